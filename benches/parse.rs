@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// A flat document of mixed key-value pairs, like a large settings file.
+fn flat(count: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        match i % 5 {
+            0 => writeln!(text, "string_{i} = \"value number {i}\""),
+            1 => writeln!(text, "integer_{i} = {}", i * 1_000),
+            2 => writeln!(text, "float_{i} = {}.{}", i, i % 97),
+            3 => writeln!(text, "bool_{i} = {}", i % 2 == 0),
+            _ => writeln!(text, "'literal {i}' = 'C:\\Users\\{i}' # comment"),
+        }
+        .unwrap();
+    }
+    text
+}
+
+/// Tables nested `depth` levels deep, each with a few keys of its own.
+fn nested(depth: usize) -> String {
+    let mut text = String::new();
+    let mut path = Vec::new();
+    for i in 0..depth {
+        path.push(format!("level_{i}"));
+        writeln!(text, "[{}]", path.join(".")).unwrap();
+        writeln!(text, "name = \"level {i}\"").unwrap();
+        writeln!(text, "index = {i}").unwrap();
+        writeln!(text, "point = {{ x = {i}, y = {} }}\n", i * 2).unwrap();
+    }
+    text
+}
+
+/// Every flavour of date and time, as found in event logs or schedules.
+fn datetimes(count: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        let day = i % 28 + 1;
+        let hour = i % 24;
+        let minute = i % 60;
+        writeln!(text, "[event_{i}]").unwrap();
+        writeln!(text, "at = 1979-05-{day:02}T{hour:02}:{minute:02}:00Z").unwrap();
+        writeln!(
+            text,
+            "offset = 1979-05-{day:02} {hour:02}:32:00.999999-07:00"
+        )
+        .unwrap();
+        writeln!(text, "local = 1979-05-{day:02}T{hour:02}:{minute:02}:00").unwrap();
+        writeln!(text, "date = 1979-05-{day:02}").unwrap();
+        writeln!(text, "time = {hour:02}:{minute:02}:00.5\n").unwrap();
+    }
+    text
+}
+
+/// A long array of tables, like a list of products or servers.
+fn array_of_tables(count: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        writeln!(text, "[[products]]").unwrap();
+        writeln!(text, "name = \"product {i}\"").unwrap();
+        writeln!(text, "sku = {}", 100_000 + i).unwrap();
+        writeln!(text, "tags = [\"tag-{}\", \"tag-{}\"]", i % 7, i % 11).unwrap();
+        writeln!(text, "dimensions = {{ width = {i}.5, height = 2.0 }}\n").unwrap();
+    }
+    text
+}
+
+/// A few keys holding very long arrays, such as lookup tables or samples.
+fn big_arrays(count: usize, len: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        let values: Vec<_> = (0..len).map(|j| format!("\"sample {i}-{j}\"")).collect();
+        writeln!(text, "samples_{i} = [{}]", values.join(", ")).unwrap();
+    }
+    text
+}
+
+/// Many sibling tables, which stresses the bookkeeping of defined tables.
+fn many_tables(count: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        writeln!(text, "[servers.server_{i}]").unwrap();
+        writeln!(text, "ip = \"10.0.{}.{}\"", i / 256, i % 256).unwrap();
+        writeln!(text, "role = \"backend\"\n").unwrap();
+    }
+    text
+}
+
+/// Tables with prefix-sharing names alongside inline tables and arrays of
+/// tables, so every kind of defined-table lookup is exercised.
+fn mixed_tables(count: usize) -> String {
+    let mut text = String::new();
+    for i in 0..count {
+        writeln!(text, "[app_{i}]").unwrap();
+        writeln!(text, "limits = {{ cpu = {i}, memory = \"{i}Mi\" }}").unwrap();
+        writeln!(text, "[app_{i}0.env]").unwrap();
+        writeln!(text, "mode = \"release\"").unwrap();
+        writeln!(text, "[[app_{i}.ports]]").unwrap();
+        writeln!(text, "number = {}\n", 8_000 + i).unwrap();
+    }
+    text
+}
+
+fn parse(c: &mut Criterion) {
+    let documents = [
+        ("flat", flat(5_000)),
+        ("nested", nested(200)),
+        ("datetimes", datetimes(1_000)),
+        ("array_of_tables", array_of_tables(1_000)),
+        ("big_arrays", big_arrays(20, 1_000)),
+        ("many_tables", many_tables(1_000)),
+        ("mixed_tables", mixed_tables(500)),
+    ];
+    let mut group = c.benchmark_group("parse");
+    for (name, text) in &documents {
+        toml::from_str(text).expect("benchmark document should parse");
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_function(*name, |b| b.iter(|| toml::from_str(black_box(text))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,238 @@
+//! Temporal types used by [`Value`](crate::Value).
+//!
+//! With the `chrono` feature enabled (the default), dates and times are
+//! represented with chrono types. Without it, they are stored as a
+//! [`Datetime`] holding the validated source text.
+
+use crate::error::{Error, Result};
+
+#[cfg(feature = "chrono")]
+pub type OffsetDateTime = chrono::DateTime<chrono::FixedOffset>;
+#[cfg(feature = "chrono")]
+pub type LocalDateTime = chrono::NaiveDateTime;
+#[cfg(feature = "chrono")]
+pub type LocalDate = chrono::NaiveDate;
+#[cfg(feature = "chrono")]
+pub type LocalTime = chrono::NaiveTime;
+
+#[cfg(not(feature = "chrono"))]
+pub type OffsetDateTime = Datetime;
+#[cfg(not(feature = "chrono"))]
+pub type LocalDateTime = Datetime;
+#[cfg(not(feature = "chrono"))]
+pub type LocalDate = Datetime;
+#[cfg(not(feature = "chrono"))]
+pub type LocalTime = Datetime;
+
+#[cfg(feature = "chrono")]
+pub fn parse_offset_date_time(text: &str) -> Result<OffsetDateTime> {
+    chrono::DateTime::parse_from_rfc3339(&text.replace(' ', "T")).map_err(|_| Error::Parse)
+}
+
+#[cfg(feature = "chrono")]
+pub fn parse_local_date_time(text: &str) -> Result<LocalDateTime> {
+    chrono::NaiveDateTime::parse_from_str(&text.replace(' ', "T"), "%Y-%m-%dT%H:%M:%S%.f")
+        .map_err(|_| Error::Parse)
+}
+
+#[cfg(feature = "chrono")]
+pub fn parse_local_date(text: &str) -> Result<LocalDate> {
+    chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| Error::Parse)
+}
+
+#[cfg(feature = "chrono")]
+pub fn parse_local_time(text: &str) -> Result<LocalTime> {
+    chrono::NaiveTime::parse_from_str(text, "%H:%M:%S%.f").map_err(|_| Error::Parse)
+}
+
+/// A date, time, or date-time value stored as validated TOML text.
+///
+/// The date/time separator is normalized to `T` and a `z` offset to `Z`;
+/// everything else is kept as written.
+#[cfg(not(feature = "chrono"))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Datetime(String);
+
+#[cfg(not(feature = "chrono"))]
+impl Datetime {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+impl std::fmt::Display for Datetime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+pub fn parse_offset_date_time(text: &str) -> Result<OffsetDateTime> {
+    let (date_time, offset) = match text.strip_suffix('Z') {
+        Some(date_time) => (date_time, None),
+        None if text.len() >= 6 && text.is_char_boundary(text.len() - 6) => {
+            let (date_time, offset) = text.split_at(text.len() - 6);
+            (date_time, Some(offset))
+        }
+        None => return Err(Error::Parse),
+    };
+    if let Some(offset) = offset {
+        validate_offset(offset)?;
+    }
+    validate_date_time(date_time)?;
+    Ok(Datetime(normalize_separator(text)))
+}
+
+#[cfg(not(feature = "chrono"))]
+pub fn parse_local_date_time(text: &str) -> Result<LocalDateTime> {
+    validate_date_time(text)?;
+    Ok(Datetime(normalize_separator(text)))
+}
+
+#[cfg(not(feature = "chrono"))]
+pub fn parse_local_date(text: &str) -> Result<LocalDate> {
+    validate_date(text)?;
+    Ok(Datetime(text.into()))
+}
+
+#[cfg(not(feature = "chrono"))]
+pub fn parse_local_time(text: &str) -> Result<LocalTime> {
+    validate_time(text)?;
+    Ok(Datetime(text.into()))
+}
+
+#[cfg(not(feature = "chrono"))]
+fn normalize_separator(text: &str) -> String {
+    let mut text = text.to_string();
+    if text.len() > 10 && text.is_char_boundary(10) && text.is_char_boundary(11) {
+        text.replace_range(10..11, "T");
+    }
+    text
+}
+
+#[cfg(not(feature = "chrono"))]
+fn number(text: &str, max: u32) -> Result<u32> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::Parse);
+    }
+    let number = text.parse().map_err(|_| Error::Parse)?;
+    if number > max {
+        return Err(Error::Parse);
+    }
+    Ok(number)
+}
+
+#[cfg(not(feature = "chrono"))]
+fn validate_date_time(text: &str) -> Result<()> {
+    if !text.is_ascii() || text.len() < 11 || !matches!(&text[10..11], "T" | " ") {
+        return Err(Error::Parse);
+    }
+    validate_date(&text[..10])?;
+    validate_time(&text[11..])
+}
+
+#[cfg(not(feature = "chrono"))]
+fn validate_date(text: &str) -> Result<()> {
+    if !text.is_ascii() || text.len() != 10 || &text[4..5] != "-" || &text[7..8] != "-" {
+        return Err(Error::Parse);
+    }
+    let year = number(&text[..4], 9999)?;
+    let month = number(&text[5..7], 12)?;
+    let day = number(&text[8..], 31)?;
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return Err(Error::Parse),
+    };
+    if day == 0 || day > days_in_month {
+        return Err(Error::Parse);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "chrono"))]
+fn validate_time(text: &str) -> Result<()> {
+    if !text.is_ascii() || text.len() < 8 || &text[2..3] != ":" || &text[5..6] != ":" {
+        return Err(Error::Parse);
+    }
+    number(&text[..2], 23)?;
+    number(&text[3..5], 59)?;
+    // allow for a leap second
+    number(&text[6..8], 60)?;
+    match &text[8..] {
+        "" => Ok(()),
+        fraction => match fraction.strip_prefix('.') {
+            Some(digits) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+                Ok(())
+            }
+            _ => Err(Error::Parse),
+        },
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+fn validate_offset(text: &str) -> Result<()> {
+    if !text.is_ascii() || text.len() != 6 || !matches!(&text[..1], "+" | "-") || &text[3..4] != ":"
+    {
+        return Err(Error::Parse);
+    }
+    number(&text[1..3], 23)?;
+    number(&text[4..], 59)?;
+    Ok(())
+}
+
+#[cfg(all(test, not(feature = "chrono")))]
+mod tests {
+    use crate::{
+        datetime::{
+            parse_local_date, parse_local_date_time, parse_local_time, parse_offset_date_time,
+        },
+        error::Result,
+    };
+
+    #[test]
+    fn offset_date_time() -> Result<()> {
+        let dt = parse_offset_date_time("1979-05-27T07:32:00Z")?;
+        assert_eq!(dt.as_str(), "1979-05-27T07:32:00Z");
+        let dt = parse_offset_date_time("1979-05-27 00:32:00.999999-07:00")?;
+        assert_eq!(dt.as_str(), "1979-05-27T00:32:00.999999-07:00");
+        assert!(parse_offset_date_time("1979-05-27T07:32:00+24:00").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn local_date_time() -> Result<()> {
+        let dt = parse_local_date_time("1979-05-27T07:32:00")?;
+        assert_eq!(dt.as_str(), "1979-05-27T07:32:00");
+        let dt = parse_local_date_time("1979-05-27 00:32:00.999999")?;
+        assert_eq!(dt.as_str(), "1979-05-27T00:32:00.999999");
+        assert!(parse_local_date_time("1979-05-27T25:32:00").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn local_date() -> Result<()> {
+        assert_eq!(parse_local_date("1979-05-27")?.as_str(), "1979-05-27");
+        assert_eq!(parse_local_date("2000-02-29")?.as_str(), "2000-02-29");
+        assert!(parse_local_date("1900-02-29").is_err());
+        assert!(parse_local_date("1979-13-01").is_err());
+        assert!(parse_local_date("1979-04-31").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn local_time() -> Result<()> {
+        assert_eq!(parse_local_time("07:32:00")?.as_str(), "07:32:00");
+        assert_eq!(
+            parse_local_time("00:32:00.999999")?.as_str(),
+            "00:32:00.999999"
+        );
+        assert!(parse_local_time("07:60:00").is_err());
+        assert!(parse_local_time("07:32:00.").is_err());
+        Ok(())
+    }
+}
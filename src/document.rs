@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::error::Result;
+use crate::parser::{Parser, Path};
+use crate::toml::Value;
+
+/// A parsed document that remembers where each value came from in the
+/// original source.
+///
+/// Spans are recorded for every value assigned with a key-value pair outside
+/// of an inline table or array.
+#[derive(Debug)]
+pub struct Document<'a> {
+    source: &'a str,
+    root: Value,
+    spans: HashMap<Path, Range<usize>>,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn new(source: &'a str, root: Value, spans: HashMap<Path, Range<usize>>) -> Self {
+        Self {
+            source,
+            root,
+            spans,
+        }
+    }
+
+    pub fn parse(source: &'a str) -> Result<Self> {
+        Parser::document(source)
+    }
+
+    pub fn root(&self) -> &Value {
+        &self.root
+    }
+
+    pub fn into_root(self) -> Value {
+        self.root
+    }
+
+    /// Returns the source text of the value at a dotted path such as
+    /// `servers.0.host`, where numeric segments index into arrays of tables.
+    pub fn source_of(&self, path: &str) -> Option<&'a str> {
+        let path: Path = path.split('.').map(String::from).collect();
+        let span = self.spans.get(&path)?;
+        Some(&self.source[span.clone()])
+    }
+}
@@ -0,0 +1,59 @@
+use crate::toml::Value;
+
+/// Expands `${VAR}` references in every string value of a document.
+///
+/// Each reference is replaced with the result of `lookup`. References to
+/// variables that `lookup` doesn't know are left as written, and `$$` is an
+/// escape for a literal `$`. Keys are never expanded.
+///
+/// ```rust
+/// use toml::{expand_env, from_str};
+///
+/// let mut toml = from_str(r#"url = "postgres://${DB_HOST}/db""#).unwrap();
+/// expand_env(&mut toml, |var| (var == "DB_HOST").then(|| "localhost".to_string()));
+/// assert_eq!(toml["url"].as_str(), "postgres://localhost/db");
+/// ```
+pub fn expand_env(value: &mut Value, lookup: impl Fn(&str) -> Option<String>) {
+    expand(value, &lookup);
+}
+
+fn expand(value: &mut Value, lookup: &impl Fn(&str) -> Option<String>) {
+    match value {
+        Value::String(string) => *string = expand_str(string, lookup),
+        Value::Array(array) => array.iter_mut().for_each(|value| expand(value, lookup)),
+        Value::Table(table) => table.values_mut().for_each(|value| expand(value, lookup)),
+        _ => {}
+    }
+}
+
+fn expand_str(text: &str, lookup: &impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(ix) = rest.find('$') {
+        expanded.push_str(&rest[..ix]);
+        rest = &rest[ix..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            expanded.push('$');
+            rest = after;
+            continue;
+        }
+        let reference = rest
+            .strip_prefix("${")
+            .and_then(|after| after.find('}').map(|end| &after[..end]));
+        match reference {
+            Some(var) => {
+                match lookup(var) {
+                    Some(replacement) => expanded.push_str(&replacement),
+                    None => expanded.push_str(&rest[..var.len() + 3]),
+                }
+                rest = &rest[var.len() + 3..];
+            }
+            None => {
+                expanded.push('$');
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
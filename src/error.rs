@@ -1,8 +1,187 @@
+use std::fmt::{self, Display};
+
+/// A line and column in the source text, both starting at 1.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 /// Error type for the toml crate.
 #[derive(Debug, PartialEq)]
 pub enum Error {
     Parse,
+    InvalidValue(String),
+    UnterminatedString(Position),
+    UnterminatedArray(Position),
+    MissingKey(String),
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    Custom(String),
+    WrongType {
+        path: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    UnexpectedKey(String),
+    MissingValue(Position),
+    OutOfRange {
+        value: i64,
+        target: &'static str,
+    },
+    InputTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    UnexpectedToken {
+        token: String,
+        context: &'static str,
+        position: Position,
+    },
+    MalformedFloat {
+        reason: &'static str,
+        position: Position,
+    },
+    NumericKey {
+        key: String,
+        position: Position,
+    },
+    TrailingCharacters {
+        value: String,
+        position: Position,
+    },
+    NewlineInInlineTable(Position),
+    ExpectedEquals {
+        found: String,
+        position: Position,
+    },
+    DuplicateKey(String),
+    InvalidOffset {
+        offset: String,
+        position: Position,
+    },
+    MissingEquals {
+        key: String,
+        position: Position,
+    },
+    InvalidEscape {
+        escape: String,
+        position: Position,
+    },
+    ControlCharacter {
+        character: char,
+        position: Position,
+    },
+    /// A parse error along with where it happened and the offending line.
+    Syntax {
+        position: Position,
+        line: String,
+    },
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse => write!(f, "parse error"),
+            Error::InvalidValue(value) => write!(f, "invalid value `{value}`"),
+            Error::UnterminatedString(pos) => write!(f, "unterminated string starting at {pos}"),
+            Error::UnterminatedArray(pos) => write!(f, "unterminated array starting at {pos}"),
+            Error::MissingKey(path) => write!(f, "missing key `{path}`"),
+            Error::UnexpectedType { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            Error::Custom(msg) => write!(f, "{msg}"),
+            Error::WrongType {
+                path,
+                expected,
+                found,
+            } => write!(f, "expected {expected} at `{path}`, found {found}"),
+            Error::UnexpectedKey(key) => write!(f, "unexpected key `{key}`"),
+            Error::MissingValue(pos) => write!(f, "missing value at {pos}"),
+            Error::OutOfRange { value, target } => {
+                write!(f, "integer {value} is out of range for {target}")
+            }
+            Error::InputTooLarge { size, limit } => {
+                write!(
+                    f,
+                    "input of {size} bytes exceeds the limit of {limit} bytes"
+                )
+            }
+            Error::UnexpectedToken {
+                token,
+                context,
+                position,
+            } => write!(f, "unexpected `{token}` {context} ({position})"),
+            Error::MalformedFloat { reason, position } => write!(f, "{reason} ({position})"),
+            Error::NumericKey { key, position } => write!(
+                f,
+                "key `{key}` is made of numbers; did you mean a float value? ({position})"
+            ),
+            Error::TrailingCharacters { value, position } => write!(
+                f,
+                "unexpected characters after number in `{value}` ({position})"
+            ),
+            Error::NewlineInInlineTable(pos) => write!(
+                f,
+                "newlines are not allowed in inline tables (TOML 1.0) at {pos}"
+            ),
+            Error::ExpectedEquals { found, position } => {
+                write!(f, "expected `=` or `.`, found key `{found}` ({position})")
+            }
+            Error::DuplicateKey(path) => write!(f, "duplicate key `{path}`"),
+            Error::InvalidOffset { offset, position } => {
+                write!(f, "invalid offset `{offset}` in date-time ({position})")
+            }
+            Error::MissingEquals { key, position } => {
+                write!(f, "expected `=` after key `{key}` ({position})")
+            }
+            Error::InvalidEscape { escape, position } => {
+                write!(f, "invalid escape `{escape}` ({position})")
+            }
+            Error::ControlCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid control character U+{:04X} ({position})",
+                *character as u32
+            ),
+            Error::Syntax { position, line } => {
+                writeln!(f, "parse error at {position}")?;
+                write_snippet(f, *position, line)
+            }
+        }
+    }
+}
+
+/// Writes the line with a caret under the column, in the style of rustc:
+///
+/// ```text
+/// 2 | [[products]] name = "Hammer"
+///   |              ^
+/// ```
+fn write_snippet(f: &mut fmt::Formatter<'_>, position: Position, line: &str) -> fmt::Result {
+    let number = position.line.to_string();
+    let gutter = " ".repeat(number.len());
+    // keep tabs so the caret lines up however the terminal renders them
+    let indent: String = line
+        .chars()
+        .take(position.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    writeln!(f, "{number} | {line}")?;
+    write!(f, "{gutter} | {indent}^")
+}
+
+impl std::error::Error for Error {}
+
 /// Result type for the toml crate.
 pub type Result<T> = std::result::Result<T, Error>;
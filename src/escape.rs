@@ -0,0 +1,80 @@
+//! Escaping and unescaping the content of basic strings.
+
+use crate::error::{Error, Result};
+
+/// Escapes `string` for use between the quotes of a basic string. Quotes,
+/// backslashes and control characters are escaped; everything else is kept
+/// as it is.
+///
+/// ```rust
+/// use toml::escape_basic_string;
+///
+/// assert_eq!(escape_basic_string("say \"hi\"\n"), r#"say \"hi\"\n"#);
+/// ```
+pub fn escape_basic_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '\u{0008}' => escaped.push_str("\\b"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\u{000C}' => escaped.push_str("\\f"),
+            '\r' => escaped.push_str("\\r"),
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Decodes the escape sequences in the content of a basic string, without
+/// its quotes. Fails with [`Error::InvalidValue`] naming the escape if it is
+/// unknown, or if a `\u` or `\U` code is not a unicode scalar value, such as
+/// a surrogate.
+///
+/// ```rust
+/// use toml::unescape_basic_string;
+///
+/// assert_eq!(unescape_basic_string(r"café\t").unwrap(), "café\t");
+/// assert!(unescape_basic_string(r"\x").is_err());
+/// ```
+pub fn unescape_basic_string(text: &str) -> Result<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.char_indices();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        let invalid = |end: usize| Error::InvalidValue(text[start..end].into());
+        let Some((_, kind)) = chars.next() else {
+            return Err(invalid(text.len()));
+        };
+        let c = match kind {
+            'b' => '\u{0008}',
+            't' => '\t',
+            'n' => '\n',
+            'f' => '\u{000C}',
+            'r' => '\r',
+            '"' => '"',
+            '\\' => '\\',
+            'u' | 'U' => {
+                let digits = if kind == 'u' { 4 } else { 8 };
+                let code: String = chars.by_ref().take(digits).map(|(_, c)| c).collect();
+                let end = start + 2 + code.len();
+                if code.chars().count() != digits || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(invalid(end));
+                }
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(end))?
+            }
+            kind => return Err(invalid(start + 1 + kind.len_utf8())),
+        };
+        unescaped.push(c);
+    }
+    Ok(unescaped)
+}
@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::error::{Error, Result};
+use crate::datetime::{self, LocalDate, LocalDateTime, LocalTime, OffsetDateTime};
+use crate::error::{Error, Position, Result};
+use crate::escape::unescape_basic_string;
 
 #[derive(Debug, PartialEq)]
 pub enum Token {
@@ -16,14 +17,47 @@ pub enum Token {
     RightBrace,
     LeftBracket,
     RightBracket,
-    String(String),
+    String(String, StringKind),
     Integer(i64),
     Float(f64),
     Bool(bool),
-    OffsetDateTime(DateTime<FixedOffset>),
-    LocalDateTime(NaiveDateTime),
-    LocalDate(NaiveDate),
-    LocalTime(NaiveTime),
+    OffsetDateTime(OffsetDateTime),
+    LocalDateTime(LocalDateTime),
+    LocalDate(LocalDate),
+    LocalTime(LocalTime),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Newline => f.write_str("newline"),
+            Token::Equal => f.write_str("="),
+            Token::Dot => f.write_str("."),
+            Token::Comma => f.write_str(","),
+            Token::LeftBrace => f.write_str("{"),
+            Token::RightBrace => f.write_str("}"),
+            Token::LeftBracket => f.write_str("["),
+            Token::RightBracket => f.write_str("]"),
+            Token::String(string, _) => f.write_str(string),
+            Token::Integer(integer) => write!(f, "{integer}"),
+            Token::Float(float) => write!(f, "{float}"),
+            Token::Bool(bool) => write!(f, "{bool}"),
+            Token::OffsetDateTime(datetime) => write!(f, "{datetime}"),
+            Token::LocalDateTime(datetime) => write!(f, "{datetime}"),
+            Token::LocalDate(date) => write!(f, "{date}"),
+            Token::LocalTime(time) => write!(f, "{time}"),
+        }
+    }
+}
+
+/// The syntax a string token was written in.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StringKind {
+    Bare,
+    Basic,
+    Literal,
+    MultilineBasic,
+    MultilineLiteral,
 }
 
 #[derive(Clone)]
@@ -41,11 +75,18 @@ pub struct Context {
 pub struct Lexer<'a> {
     text: &'a str,
     pos: usize,
+    token_start: usize,
+    pub strict_date_times: bool,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(text: &'a str) -> Self {
-        Self { text, pos: 0 }
+        Self {
+            text,
+            pos: 0,
+            token_start: 0,
+            strict_date_times: false,
+        }
     }
 
     pub fn next(&mut self, context: Context) -> Result<Option<Token>> {
@@ -56,6 +97,7 @@ impl<'a> Lexer<'a> {
             self.pos += len;
         }
 
+        self.token_start = self.pos;
         if self.pos == self.text.len() {
             return Ok(None);
         }
@@ -65,6 +107,10 @@ impl<'a> Lexer<'a> {
             return Ok(Some(token));
         }
 
+        if matches!(context.posture, Some(Posture::Value)) {
+            self.check_decimal_point()?;
+        }
+
         if let Some(token) = self.scan_punct() {
             self.pos += 1;
             return Ok(Some(token));
@@ -82,6 +128,10 @@ impl<'a> Lexer<'a> {
             return Ok(Some(token));
         }
 
+        if self.remainder().starts_with("\"\"\"") {
+            return Err(self.multiline_basic_string_error());
+        }
+
         if let Some((token, len)) = self.scan_basic_string() {
             self.pos += len;
             return Ok(Some(token));
@@ -92,11 +142,19 @@ impl<'a> Lexer<'a> {
             return Ok(Some(token));
         }
 
+        if self.remainder().starts_with("'''") {
+            return Err(self.multiline_literal_string_error());
+        }
+
         if let Some((token, len)) = self.scan_literal_string() {
             self.pos += len;
             return Ok(Some(token));
         }
 
+        if let Some(err) = self.literal_string_error() {
+            return Err(err);
+        }
+
         if let Some((token, len)) = self.scan_offset_date_time()? {
             self.pos += len;
             return Ok(Some(token));
@@ -118,28 +176,23 @@ impl<'a> Lexer<'a> {
         }
 
         if let Some((token, len)) = self.scan_float()? {
-            self.pos += len;
-            return Ok(Some(token));
+            return self.finish_float(token, len);
         }
 
         if let Some((token, len)) = self.scan_integer_hex()? {
-            self.pos += len;
-            return Ok(Some(token));
+            return self.finish_number(token, len);
         }
 
         if let Some((token, len)) = self.scan_integer_octal()? {
-            self.pos += len;
-            return Ok(Some(token));
+            return self.finish_number(token, len);
         }
 
         if let Some((token, len)) = self.scan_integer_binary()? {
-            self.pos += len;
-            return Ok(Some(token));
+            return self.finish_number(token, len);
         }
 
         if let Some((token, len)) = self.scan_integer()? {
-            self.pos += len;
-            return Ok(Some(token));
+            return self.finish_number(token, len);
         }
 
         if let Some((token, len)) = self.scan_true() {
@@ -152,6 +205,10 @@ impl<'a> Lexer<'a> {
             return Ok(Some(token));
         }
 
+        if matches!(context.posture, Some(Posture::Value)) {
+            return Err(Error::InvalidValue(self.scan_word().into()));
+        }
+
         Err(Error::Parse)
     }
 
@@ -162,6 +219,40 @@ impl<'a> Lexer<'a> {
         token
     }
 
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the line and column of the current position.
+    pub fn position(&self) -> Position {
+        self.position_at(self.pos)
+    }
+
+    /// Returns the line and column where the most recently lexed (or peeked)
+    /// token starts.
+    pub fn token_position(&self) -> Position {
+        self.position_at(self.token_start)
+    }
+
+    /// Returns the full text of the given line, without its line ending.
+    pub fn line_text(&self, line: usize) -> &'a str {
+        let line = self.text.split('\n').nth(line - 1).unwrap_or_default();
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    fn position_at(&self, pos: usize) -> Position {
+        let consumed = &self.text[..pos];
+        let line_start = consumed.rfind('\n').map_or(0, |ix| ix + 1);
+        Position {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+        }
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.pos += self.scan_whitespace();
+    }
+
     fn remainder(&self) -> &str {
         &self.text[self.pos..]
     }
@@ -181,16 +272,52 @@ impl<'a> Lexer<'a> {
                 "(?x)
                 ^               # start
                 \\#             # delimiter
-                .*              # body
-                (\r\n|\n|$)     # newline or eof
+                [^\\r\\n]*      # body, excluding the newline
                 "
             )
             .expect("comment re should be valid");
         }
-        let captures = COMMENT_RE.captures(&self.remainder())?;
-        let comment = captures.get(0)?.as_str();
-        let ending_len = captures.get(1)?.len();
-        Some(comment.len() - ending_len)
+        let comment = COMMENT_RE.find(self.remainder())?;
+        Some(comment.len())
+    }
+
+    /// Consumes a number token, unless more of the word follows it, as in
+    /// `+0x0` or `1.5x`. Then the whole word is reported as invalid rather
+    /// than leaving the rest to fail as a confusing second token.
+    fn finish_number(&mut self, token: Token, len: usize) -> Result<Option<Token>> {
+        if self.word_continues(len) {
+            return Err(Error::InvalidValue(self.scan_word().into()));
+        }
+        self.pos += len;
+        Ok(Some(token))
+    }
+
+    /// Consumes a float token, as [`Lexer::finish_number`] does, but reports
+    /// a second decimal point or exponent, as in `1.2.3` or `1e2e3`, as
+    /// trailing characters.
+    fn finish_float(&mut self, token: Token, len: usize) -> Result<Option<Token>> {
+        if self.word_continues(len) {
+            return Err(Error::TrailingCharacters {
+                value: self.scan_word().into(),
+                position: self.position(),
+            });
+        }
+        self.pos += len;
+        Ok(Some(token))
+    }
+
+    /// Checks whether the word at the current position goes on past `len`.
+    fn word_continues(&self, len: usize) -> bool {
+        let rest = &self.remainder()[len..];
+        rest.starts_with(|c: char| !c.is_whitespace() && !matches!(c, ',' | ']' | '}' | '#'))
+    }
+
+    fn scan_word(&self) -> &str {
+        let remainder = self.remainder();
+        let len = remainder
+            .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}' | '#'))
+            .unwrap_or(remainder.len());
+        &remainder[..len]
     }
 
     fn scan_newline(&self) -> Option<(Token, usize)> {
@@ -220,9 +347,9 @@ impl<'a> Lexer<'a> {
             static ref BARE_KEY_RE: Regex =
                 Regex::new("^[[:alnum:]-_]+").expect("bare key re should be valid");
         }
-        let captures = BARE_KEY_RE.captures(&self.remainder())?;
+        let captures = BARE_KEY_RE.captures(self.remainder())?;
         let key = captures.get(0)?.as_str();
-        Some((Token::String(key.into()), key.len()))
+        Some((Token::String(key.into(), StringKind::Bare), key.len()))
     }
 
     fn scan_basic_string(&self) -> Option<(Token, usize)> {
@@ -234,7 +361,7 @@ impl<'a> Lexer<'a> {
                 (       # content
                     (?:
                         [^"\\\n]                            # general
-                        |\\(?:[btnfr"\\]|u[0-9a-fA-F]{4})   # escapes
+                        |\\(?:[btnfr"\\]|u[0-9a-fA-F]{4}|U[0-9a-fA-F]{8})   # escapes
                     )*
                 )
                 "       # close quote
@@ -242,31 +369,15 @@ impl<'a> Lexer<'a> {
             )
             .expect("basic re should be valid");
         }
-        let captures = BASIC_STR_RE.captures(&self.remainder())?;
+        let captures = BASIC_STR_RE.captures(self.remainder())?;
         let text = captures.get(0)?.as_str();
         let str = captures.get(1)?.as_str();
-        let str = str
-            .replace("\\b", "\u{0008}")
-            .replace("\\t", "\t")
-            .replace("\\n", "\n")
-            .replace("\\f", "\u{000C}")
-            .replace("\\r", "\r")
-            .replace("\\\"", "\"")
-            .replace("\\\\", "\\");
-        Some((Token::String(str), text.len()))
+        let str = unescape_basic_string(str).ok()?;
+        Some((Token::String(str, StringKind::Basic), text.len()))
     }
 
     fn scan_multiline_basic_string(&self) -> Option<(Token, usize)> {
         lazy_static! {
-            static ref MULTILINE_BASIC_STR_RE: Regex = Regex::new(
-                r#"(?xs)
-                ^           # start
-                "{3}        # open delim
-                .*          # content
-                "{3,}       # close delim
-                "#
-            )
-            .expect("multiline basic re should be valid");
             static ref LINE_ENDING_SLASH: Regex = Regex::new(
                 r#"(?x)
                 \\              # delim
@@ -277,25 +388,97 @@ impl<'a> Lexer<'a> {
             )
             .expect("line ending slash re should be valid");
         }
-        let captures = MULTILINE_BASIC_STR_RE.captures(&self.remainder())?;
-        let text = captures.get(0)?.as_str();
+        let len = Lexer::multiline_basic_string_len(self.remainder())?;
+        let text = &self.remainder()[..len];
         let content = &text[3..text.len() - 3];
-        let content = content.strip_prefix("\n").unwrap_or(content);
+        let content = content
+            .strip_prefix("\r\n")
+            .or_else(|| content.strip_prefix('\n'))
+            .unwrap_or(content);
         let content = LINE_ENDING_SLASH.replace_all(content, "");
 
         if Lexer::contains_three_consec_delims(&content) {
             return None;
         }
 
-        let content = content
-            .replace("\\b", "\u{0008}")
-            .replace("\\t", "\t")
-            .replace("\\n", "\n")
-            .replace("\\f", "\u{000C}")
-            .replace("\\r", "\r")
-            .replace("\\\"", "\"")
-            .replace("\\\\", "\\");
-        Some((Token::String(content.into()), text.len()))
+        let content = unescape_basic_string(&content).ok()?;
+        Some((
+            Token::String(content, StringKind::MultilineBasic),
+            text.len(),
+        ))
+    }
+
+    /// Finds the length of a multiline basic string at the start of `text`,
+    /// including its delimiters. The string ends at the first unescaped run
+    /// of three quotes, and up to two more quotes directly after that run are
+    /// part of the content.
+    fn multiline_basic_string_len(text: &str) -> Option<usize> {
+        let bytes = text.strip_prefix("\"\"\"")?.as_bytes();
+        let mut ix = 0;
+        while ix < bytes.len() {
+            if bytes[ix] == b'\\' {
+                ix += 2;
+            } else if bytes[ix..].starts_with(b"\"\"\"") {
+                let mut end = ix + 3;
+                while end < ix + 5 && bytes.get(end) == Some(&b'"') {
+                    end += 1;
+                }
+                return Some(end + 3);
+            } else {
+                ix += 1;
+            }
+        }
+        None
+    }
+
+    /// Explains why the multiline basic string at the current position could
+    /// not be scanned: either it is never closed, or its content holds an
+    /// invalid escape.
+    fn multiline_basic_string_error(&self) -> Error {
+        let Some(len) = Lexer::multiline_basic_string_len(self.remainder()) else {
+            return Error::UnterminatedString(self.position());
+        };
+        let content = &self.remainder()[3..len - 3];
+        match Lexer::find_invalid_escape(content) {
+            Some((ix, escape)) => Error::InvalidEscape {
+                escape,
+                position: self.position_at(self.pos + 3 + ix),
+            },
+            None => Error::UnterminatedString(self.position()),
+        }
+    }
+
+    /// Finds the first escape in the content of a multiline basic string that
+    /// cannot be decoded, returning its byte offset and text. A backslash at
+    /// the end of a line is not an escape.
+    fn find_invalid_escape(content: &str) -> Option<(usize, String)> {
+        lazy_static! {
+            static ref LINE_ENDING_SLASH_RE: Regex =
+                Regex::new(r"^\\[ \t]*\r?\n").expect("line ending slash re should be valid");
+        }
+        let mut ix = 0;
+        while let Some(offset) = content[ix..].find('\\') {
+            ix += offset;
+            let rest = &content[ix..];
+            if let Some(found) = LINE_ENDING_SLASH_RE.find(rest) {
+                ix += found.len();
+                continue;
+            }
+            let escape_chars = match rest[1..].chars().next() {
+                Some('u') => 6,
+                Some('U') => 10,
+                _ => 2,
+            };
+            let end = rest
+                .char_indices()
+                .nth(escape_chars)
+                .map_or(rest.len(), |(end, _)| end);
+            if let Err(Error::InvalidValue(escape)) = unescape_basic_string(&rest[..end]) {
+                return Some((ix, escape));
+            }
+            ix += end;
+        }
+        None
     }
 
     fn scan_literal_string(&self) -> Option<(Token, usize)> {
@@ -304,16 +487,18 @@ impl<'a> Lexer<'a> {
                 r#"(?x)
                 ^          # start
                 '          # open quote
-                ([^'\n]*)  # content
+                (          # content, any char but controls other than tab
+                    [^'\x00-\x08\x0A-\x1F\x7F]*
+                )
                 '          # close quote
                 "#
             )
             .expect("literal str re should be valid");
         }
-        let captures = LITERAL_STR_RE.captures(&self.remainder())?;
+        let captures = LITERAL_STR_RE.captures(self.remainder())?;
         let text = captures.get(0).unwrap().as_str();
         let str = captures.get(1).unwrap().as_str();
-        Some((Token::String(str.into()), text.len()))
+        Some((Token::String(str.into(), StringKind::Literal), text.len()))
     }
 
     fn scan_multiline_literal_string(&self) -> Option<(Token, usize)> {
@@ -323,17 +508,69 @@ impl<'a> Lexer<'a> {
                 ^       # start
                 '{3}    # open delim
                 \n?     # initial newline
-                .*?     # content
+                (?:     # content, any char but controls other than tab and newline
+                    [^\x00-\x08\x0B-\x1F\x7F]
+                    |\r\n
+                )*?
                 '{3,}   # close delim
                 "
             )
             .expect("multiline literal str re should be valid");
         }
-        let captures = MULTILINE_LITERAL_STR_RE.captures(&self.remainder())?;
+        let captures = MULTILINE_LITERAL_STR_RE.captures(self.remainder())?;
         let text = captures.get(0)?.as_str();
         let content = &text[3..text.len() - 3];
-        let content = content.strip_prefix("\n").unwrap_or(content);
-        Some((Token::String(content.into()), text.len()))
+        let content = content
+            .strip_prefix("\r\n")
+            .or_else(|| content.strip_prefix('\n'))
+            .unwrap_or(content);
+        Some((
+            Token::String(content.into(), StringKind::MultilineLiteral),
+            text.len(),
+        ))
+    }
+
+    /// Explains why the multiline literal string at the current position could
+    /// not be scanned: either it is never closed, or its content holds a
+    /// control character other than tab or a line ending.
+    fn multiline_literal_string_error(&self) -> Error {
+        let Some(len) = self.remainder()[3..].find("'''") else {
+            return Error::UnterminatedString(self.position());
+        };
+        let content = &self.remainder()[3..3 + len];
+        self.control_character_error(self.pos + 3, content)
+            .unwrap_or_else(|| Error::UnterminatedString(self.position()))
+    }
+
+    /// Finds a control character other than tab in the literal string at the
+    /// current position, if the string is closed on the same line.
+    fn literal_string_error(&self) -> Option<Error> {
+        let content = self.remainder().strip_prefix('\'')?;
+        let len = content.find(['\'', '\n'])?;
+        if !content[len..].starts_with('\'') {
+            return None;
+        }
+        self.control_character_error(self.pos + 1, &content[..len])
+    }
+
+    /// Reports the first control character in `content`, which starts at byte
+    /// `start` of the text, that is not a tab, a newline or part of a CRLF.
+    fn control_character_error(&self, start: usize, content: &str) -> Option<Error> {
+        let mut chars = content.char_indices().peekable();
+        while let Some((ix, c)) = chars.next() {
+            let forbidden = match c {
+                '\t' | '\n' => false,
+                '\r' => !matches!(chars.peek(), Some((_, '\n'))),
+                c => c.is_ascii_control(),
+            };
+            if forbidden {
+                return Some(Error::ControlCharacter {
+                    character: c,
+                    position: self.position_at(start + ix),
+                });
+            }
+        }
+        None
     }
 
     fn scan_integer(&self) -> Result<Option<(Token, usize)>> {
@@ -350,7 +587,7 @@ impl<'a> Lexer<'a> {
             )
             .expect("integer re should be valid");
         }
-        let Some(captures) = INTEGER_RE.captures(&self.remainder()) else {
+        let Some(captures) = INTEGER_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let raw = captures.get(0).unwrap().as_str();
@@ -373,7 +610,7 @@ impl<'a> Lexer<'a> {
             )
             .expect("integer hex re should be valid");
         }
-        let Some(captures) = INTEGER_HEX_RE.captures(&self.remainder()) else {
+        let Some(captures) = INTEGER_HEX_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let raw = captures.get(0).unwrap().as_str();
@@ -396,7 +633,7 @@ impl<'a> Lexer<'a> {
             )
             .expect("integer octal re should be valid");
         }
-        let Some(captures) = INTEGER_OCTAL_RE.captures(&self.remainder()) else {
+        let Some(captures) = INTEGER_OCTAL_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let raw = captures.get(0).unwrap().as_str();
@@ -419,7 +656,7 @@ impl<'a> Lexer<'a> {
             )
             .expect("integer binary re should be valid");
         }
-        let Some(captures) = INTEGER_BINARY_RE.captures(&self.remainder()) else {
+        let Some(captures) = INTEGER_BINARY_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let text = captures.get(0).unwrap().as_str();
@@ -456,10 +693,10 @@ impl<'a> Lexer<'a> {
                 }
             } else if let Some(captures) = FLOAT_INF_RE.captures(self.remainder()) {
                 Some(captures.get(0).unwrap().as_str())
-            } else if let Some(captures) = FLOAT_NAN_RE.captures(self.remainder()) {
-                Some(captures.get(0).unwrap().as_str())
             } else {
-                None
+                FLOAT_NAN_RE
+                    .captures(self.remainder())
+                    .map(|captures| captures.get(0).unwrap().as_str())
             }
         }) else {
             return Ok(None);
@@ -471,12 +708,36 @@ impl<'a> Lexer<'a> {
         Ok(Some((Token::Float(float), text.len())))
     }
 
+    /// Reports floats such as `.7` and `7.` that are missing a digit on one
+    /// side of the decimal point, which would otherwise fail as a stray `.`
+    /// or an invalid value.
+    fn check_decimal_point(&self) -> Result<()> {
+        lazy_static! {
+            static ref LEADING_POINT_RE: Regex =
+                Regex::new("^[+-]?\\.[0-9]").expect("leading point re should be valid");
+            static ref TRAILING_POINT_RE: Regex =
+                Regex::new("^[+-]?[0-9](?:_?[0-9])*\\.(?:$|[^0-9])")
+                    .expect("trailing point re should be valid");
+        }
+        let reason = if LEADING_POINT_RE.is_match(self.remainder()) {
+            "floats must have a digit before the decimal point"
+        } else if TRAILING_POINT_RE.is_match(self.remainder()) {
+            "floats must have a digit after the decimal point"
+        } else {
+            return Ok(());
+        };
+        Err(Error::MalformedFloat {
+            reason,
+            position: self.position(),
+        })
+    }
+
     fn scan_true(&self) -> Option<(Token, usize)> {
         lazy_static! {
             static ref TRUE_RE: Regex =
-                Regex::new("^true(?:$|\\s)").expect("true re should be valid");
+                Regex::new("^true(?:$|[^[:alnum:]_-])").expect("true re should be valid");
         }
-        if TRUE_RE.is_match(&self.remainder()) {
+        if TRUE_RE.is_match(self.remainder()) {
             Some((Token::Bool(true), 4))
         } else {
             None
@@ -486,9 +747,9 @@ impl<'a> Lexer<'a> {
     fn scan_false(&self) -> Option<(Token, usize)> {
         lazy_static! {
             static ref FALSE_RE: Regex =
-                Regex::new("^false(?:$|\\s)").expect("false re should be valid");
+                Regex::new("^false(?:$|[^[:alnum:]_-])").expect("false re should be valid");
         }
-        if FALSE_RE.is_match(&self.remainder()) {
+        if FALSE_RE.is_match(self.remainder()) {
             Some((Token::Bool(false), 5))
         } else {
             None
@@ -501,19 +762,40 @@ impl<'a> Lexer<'a> {
                 r"(?x)
                 ^                               # start
                 \d{4}-\d{2}-\d{2}               # date
-                [T\ ]                           # separator
+                [Tt\ ]                          # separator
                 \d{2}:\d{2}:\d{2}(?:\.\d+)?     # time
-                (?:Z|[\+-]\d{2}:\d{2})          # offset
+                (?:[Zz]|[\+-]\d{2}:\d{2})       # offset
                 "
             )
             .expect("date time re should be valid");
+            static ref ANY_OFFSET_RE: Regex = Regex::new(
+                r"(?x)
+                ^\d{4}-\d{2}-\d{2}[Tt\ ]\d{2}:\d{2}:\d{2}(?:\.\d+)?
+                ([\+-][^\s,\]\}\#]*)         # offset, up to the end of the word
+                "
+            )
+            .expect("date time offset re should be valid");
+            static ref OFFSET_RE: Regex =
+                Regex::new(r"^[\+-]\d{2}:\d{2}$").expect("offset re should be valid");
         }
-        let Some(captures) = OFFSET_DATE_TIME_RE.captures(&self.remainder()) else {
+        // report a malformed offset, such as `+07` or `+07:00:30`, itself
+        // rather than leaving part of it to fail as an unrelated value
+        if let Some(offset) = ANY_OFFSET_RE
+            .captures(self.remainder())
+            .and_then(|captures| captures.get(1))
+        {
+            if !OFFSET_RE.is_match(offset.as_str()) {
+                return Err(Error::InvalidOffset {
+                    offset: offset.as_str().into(),
+                    position: self.position_at(self.pos + offset.start()),
+                });
+            }
+        }
+        let Some(captures) = OFFSET_DATE_TIME_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let text = captures.get(0).unwrap().as_str();
-        let text = text.replace(" ", "T");
-        let dt = DateTime::parse_from_rfc3339(&text).map_err(|_| Error::Parse)?;
+        let dt = datetime::parse_offset_date_time(&self.normalize_date_time(text)?)?;
         Ok(Some((Token::OffsetDateTime(dt), text.len())))
     }
 
@@ -523,31 +805,39 @@ impl<'a> Lexer<'a> {
                 r"(?x)
                 ^                               # start
                 \d{4}-\d{2}-\d{2}               # date
-                [T\ ]                           # separator
+                [Tt\ ]                          # separator
                 \d{2}:\d{2}:\d{2}(?:\.\d+)?     # time
                 "
             )
             .expect("date time local re should be valid");
         }
-        let Some(captures) = LOCAL_DATE_TIME_RE.captures(&self.remainder()) else {
+        let Some(captures) = LOCAL_DATE_TIME_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let text = captures.get(0).unwrap().as_str();
-        let dt = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
-            .map_err(|_| Error::Parse)?;
+        let dt = datetime::parse_local_date_time(&self.normalize_date_time(text)?)?;
         Ok(Some((Token::LocalDateTime(dt), text.len())))
     }
 
+    /// Uppercases a lowercase `t` separator or `z` offset, which TOML allows
+    /// but RFC 3339 parsers may not, or rejects them in strict mode.
+    fn normalize_date_time(&self, text: &str) -> Result<String> {
+        if self.strict_date_times && text.contains(['t', 'z']) {
+            return Err(Error::InvalidValue(text.into()));
+        }
+        Ok(text.to_ascii_uppercase())
+    }
+
     fn scan_local_date(&self) -> Result<Option<(Token, usize)>> {
         lazy_static! {
             static ref LOCAL_DATE_RE: Regex =
                 Regex::new(r"^\d{4}-\d{2}-\d{2}").expect("date local re should be valid");
         }
-        let Some(captures) = LOCAL_DATE_RE.captures(&self.remainder()) else {
+        let Some(captures) = LOCAL_DATE_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let text = captures.get(0).unwrap().as_str();
-        let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| Error::Parse)?;
+        let date = datetime::parse_local_date(text)?;
         Ok(Some((Token::LocalDate(date), text.len())))
     }
 
@@ -556,22 +846,23 @@ impl<'a> Lexer<'a> {
             static ref LOCAL_TIME_RE: Regex =
                 Regex::new(r"^\d{2}:\d{2}:\d{2}(?:\.\d+)?").expect("time local re should be valid");
         }
-        let Some(captures) = LOCAL_TIME_RE.captures(&self.remainder()) else {
+        let Some(captures) = LOCAL_TIME_RE.captures(self.remainder()) else {
             return Ok(None);
         };
         let text = captures.get(0).unwrap().as_str();
-        let time = NaiveTime::parse_from_str(text, "%H:%M:%S%.f").map_err(|_| Error::Parse)?;
+        let time = datetime::parse_local_time(text)?;
         Ok(Some((Token::LocalTime(time), text.len())))
     }
 
     fn contains_three_consec_delims(text: &str) -> bool {
+        let bytes = text.as_bytes();
         let mut count = 0;
         let mut ix = 0;
-        while ix < text.len() {
-            if text[ix..].starts_with("\"") {
+        while ix < bytes.len() {
+            if bytes[ix] == b'"' {
                 count += 1;
                 ix += 1;
-            } else if text[ix..].starts_with("\\\"") {
+            } else if bytes[ix..].starts_with(b"\\\"") {
                 count = 0;
                 ix += 2;
             } else {
@@ -593,11 +884,12 @@ impl<'a> Lexer<'a> {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "chrono")]
     use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone};
 
     use crate::{
-        error::{Error, Result},
-        lexer::{Context, Lexer, Posture, Token},
+        error::{Error, Position, Result},
+        lexer::{Context, Lexer, Posture, StringKind, Token},
     };
 
     #[test]
@@ -627,12 +919,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn comment_crlf_at_eof() -> Result<()> {
+        let text = "# comment\r\n";
+        let mut lexer = Lexer::new(text);
+        assert_eq!(lexer.next(Context::default())?, Some(Token::Newline));
+        assert_eq!(lexer.pos(), text.len());
+        assert_eq!(lexer.next(Context::default())?, None);
+        Ok(())
+    }
+
     #[test]
     fn bare_key() -> Result<()> {
         let text = "key";
         let mut lexer = Lexer::new(text);
         let context = Context::default();
-        assert_eq!(lexer.next(context)?, Some(Token::String("key".into())));
+        assert_eq!(
+            lexer.next(context)?,
+            Some(Token::String("key".into(), StringKind::Bare))
+        );
         Ok(())
     }
 
@@ -704,7 +1009,10 @@ mod tests {
         let text = "'foo'";
         let mut lexer = Lexer::new(text);
         let context = Context::default();
-        assert_eq!(lexer.next(context)?, Some(Token::String("foo".into())));
+        assert_eq!(
+            lexer.next(context)?,
+            Some(Token::String("foo".into(), StringKind::Literal))
+        );
         Ok(())
     }
 
@@ -722,7 +1030,13 @@ mod tests {
         let text = "'''foo\nbar'''";
         let mut lexer = Lexer::new(text);
         let context = Context::default();
-        assert_eq!(lexer.next(context)?, Some(Token::String("foo\nbar".into())));
+        assert_eq!(
+            lexer.next(context)?,
+            Some(Token::String(
+                "foo\nbar".into(),
+                StringKind::MultilineLiteral
+            ))
+        );
         Ok(())
     }
 
@@ -731,7 +1045,10 @@ mod tests {
         let text = r#""foo""#;
         let mut lexer = Lexer::new(text);
         let context = Context::default();
-        assert_eq!(lexer.next(context)?, Some(Token::String("foo".into())));
+        assert_eq!(
+            lexer.next(context)?,
+            Some(Token::String("foo".into(), StringKind::Basic))
+        );
         Ok(())
     }
 
@@ -740,11 +1057,28 @@ mod tests {
         let text = "\"\"\"foo\nbar\"\"\"";
         let mut lexer = Lexer::new(text);
         let context = Context::default();
-        assert_eq!(lexer.next(context)?, Some(Token::String("foo\nbar".into())));
+        assert_eq!(
+            lexer.next(context)?,
+            Some(Token::String("foo\nbar".into(), StringKind::MultilineBasic))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn multiline_basic_string_unterminated() -> Result<()> {
+        let text = "\n  \"\"\"foo\nbar";
+        let mut lexer = Lexer::new(text);
+        let context = Context::default();
+        assert_eq!(lexer.next(context.clone())?, Some(Token::Newline));
+        assert_eq!(
+            lexer.next(context),
+            Err(Error::UnterminatedString(Position { line: 2, column: 3 }))
+        );
         Ok(())
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer() -> Result<()> {
         let text = "123";
         let mut lexer = Lexer::new(text);
@@ -764,6 +1098,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_negative() -> Result<()> {
         let text = "-123";
         let mut lexer = Lexer::new(text);
@@ -774,6 +1109,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_zero() -> Result<()> {
         let text = "0";
         let mut lexer = Lexer::new(text);
@@ -784,6 +1120,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_underscore() -> Result<()> {
         let text = "1_000";
         let mut lexer = Lexer::new(text);
@@ -794,6 +1131,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_hex() -> Result<()> {
         let text = "0xDEADBEEF";
         let mut lexer = Lexer::new(text);
@@ -804,6 +1142,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_octal() -> Result<()> {
         let text = "0o01234567";
         let mut lexer = Lexer::new(text);
@@ -814,6 +1153,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn integer_binary() -> Result<()> {
         let text = "0b11010110";
         let mut lexer = Lexer::new(text);
@@ -824,6 +1164,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn float() -> Result<()> {
         let text = "+1.0";
         let mut lexer = Lexer::new(text);
@@ -834,6 +1175,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn float_exp() -> Result<()> {
         let text = "6.26e-34";
         let mut lexer = Lexer::new(text);
@@ -844,6 +1186,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn float_underscore() -> Result<()> {
         let text = "224_617.445_991_228";
         let mut lexer = Lexer::new(text);
@@ -854,6 +1197,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn float_inf() -> Result<()> {
         let text = "inf +inf -inf";
         let mut lexer = Lexer::new(text);
@@ -872,6 +1216,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn bool_true() -> Result<()> {
         let text = "true";
         let mut lexer = Lexer::new(text);
@@ -882,6 +1227,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn bool_false() -> Result<()> {
         let text = "false";
         let mut lexer = Lexer::new(text);
@@ -892,6 +1238,33 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
+    fn bool_delimited() -> Result<()> {
+        let text = "true,false]";
+        let mut lexer = Lexer::new(text);
+        let mut context = Context::default();
+        context.posture = Some(Posture::Value);
+        assert_eq!(lexer.next(context.clone())?, Some(Token::Bool(true)));
+        assert_eq!(lexer.next(context.clone())?, Some(Token::Comma));
+        assert_eq!(lexer.next(context.clone())?, Some(Token::Bool(false)));
+        assert_eq!(lexer.next(context)?, Some(Token::RightBracket));
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::field_reassign_with_default)]
+    fn invalid_value() -> Result<()> {
+        let text = "off]";
+        let mut lexer = Lexer::new(text);
+        let mut context = Context::default();
+        context.posture = Some(Posture::Value);
+        assert_eq!(lexer.next(context), Err(Error::InvalidValue("off".into())));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::field_reassign_with_default, clippy::zero_prefixed_literal)]
     fn date_time_offset() -> Result<()> {
         let text = "1979-05-27T07:32:00Z";
         let mut lexer = Lexer::new(text);
@@ -906,6 +1279,8 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::field_reassign_with_default, clippy::zero_prefixed_literal)]
     fn date_time_local() -> Result<()> {
         let text = "1979-05-27T07:32:00";
         let mut lexer = Lexer::new(text);
@@ -920,6 +1295,8 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::field_reassign_with_default, clippy::zero_prefixed_literal)]
     fn date_local() -> Result<()> {
         let text = "1979-05-27";
         let mut lexer = Lexer::new(text);
@@ -931,6 +1308,8 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::field_reassign_with_default, clippy::zero_prefixed_literal)]
     fn time_local() -> Result<()> {
         let text = "07:32:00";
         let mut lexer = Lexer::new(text);
@@ -19,15 +19,86 @@
 //! assert_eq!(toml["groceries"]["cash"].as_bool(), true);
 //! ```
 
-pub use crate::toml::Value;
-pub use error::Result;
+pub use crate::toml::{Array, LineEnding, NanPolicy, Table, Value, ValueKind, ValueRef};
+#[cfg(feature = "chrono")]
+pub use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+#[cfg(not(feature = "chrono"))]
+pub use datetime::Datetime;
+#[cfg(feature = "serde")]
+pub use de::from_str_serde;
+pub use document::Document;
+pub use env::expand_env;
+pub use error::{Error, Position, Result};
+pub use escape::{escape_basic_string, unescape_basic_string};
 use parser::Parser;
+pub use parser::ParserOptions;
+#[cfg(feature = "serde")]
+pub use ser::to_string_serde;
+pub use ser::{to_string, to_string_with, SerializerOptions};
 
+mod datetime;
+#[cfg(feature = "serde")]
+mod de;
+mod document;
+mod env;
 mod error;
+mod escape;
 mod lexer;
+mod macros;
 mod parser;
+pub mod ser;
 mod toml;
 
 pub fn from_str(text: &str) -> Result<Value> {
     Parser::from_str(text)
 }
+
+/// Parses a document, as [`from_str`] does, but with the given options.
+pub fn from_str_with(text: &str, options: &ParserOptions) -> Result<Value> {
+    Parser::from_str_with(text, options)
+}
+
+/// Parses only the first `n` logical lines of a document and ignores the
+/// rest, which is useful for previewing large files. A multiline string or
+/// array counts as a single line.
+pub fn from_str_prefix_lines(text: &str, n: usize) -> Result<Value> {
+    Parser::from_str_prefix_lines(text, n)
+}
+
+/// Parses a document and checks that every top-level key is one of
+/// `allowed_top_keys`, which catches misspelled sections and settings.
+pub fn from_str_allowing(text: &str, allowed_top_keys: &[&str]) -> Result<Value> {
+    let root = from_str(text)?;
+    for (key, _) in root.sorted_entries() {
+        if !allowed_top_keys.contains(&key.as_str()) {
+            return Err(Error::UnexpectedKey(key.clone()));
+        }
+    }
+    Ok(root)
+}
+
+/// Parses `defaults` and `overrides` and deep-merges the overrides on top
+/// of the defaults, as [`Value::merge`] does. Useful for layering a user's
+/// configuration over defaults embedded in the program.
+pub fn from_str_with_defaults(defaults: &str, overrides: &str) -> Result<Value> {
+    let mut root = from_str(defaults)?;
+    root.merge(from_str(overrides)?);
+    Ok(root)
+}
+
+/// Parses several documents separated by lines equal to `separator`, such as
+/// `---` or `+++`. Each document is parsed independently.
+pub fn from_str_multi(text: &str, separator: &str) -> Result<Vec<Value>> {
+    let mut documents = Vec::new();
+    let mut chunk = String::new();
+    for line in text.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == separator {
+            documents.push(from_str(&chunk)?);
+            chunk.clear();
+        } else {
+            chunk.push_str(line);
+        }
+    }
+    documents.push(from_str(&chunk)?);
+    Ok(documents)
+}
@@ -0,0 +1,78 @@
+/// Builds a [`Value`](crate::Value) table from TOML-like syntax.
+///
+/// Entries are written as `key = value` and separated by commas. Keys are
+/// identifiers or string literals, `{ ... }` is a nested table, `[ ... ]` is
+/// an array, and anything else is an expression converted with `Value::from`.
+///
+/// ```rust
+/// use toml::{from_str, toml};
+///
+/// let port = 8080;
+/// let value = toml! {
+///     title = "TOML Example",
+///     server = { host = "localhost", port = port, "max-connections" = 10 },
+///     ratios = [0.5, -1.0],
+///     users = [{ name = "admin" }, { name = "guest" }],
+/// };
+///
+/// assert_eq!(value["server"]["port"].as_int(), 8080);
+/// assert_eq!(value["users"][1]["name"].as_str(), "guest");
+/// ```
+#[macro_export]
+macro_rules! toml {
+    // entries of a table, munched one token at a time up to each comma
+    (@table $table:ident) => {};
+    (@table $table:ident $key:tt = $($rest:tt)*) => {
+        $crate::toml!(@entry $table $key () $($rest)*)
+    };
+    (@entry $table:ident $key:tt ($($value:tt)+) , $($rest:tt)*) => {
+        $table.insert($crate::toml!(@key $key), $crate::toml!(@value $($value)+));
+        $crate::toml!(@table $table $($rest)*);
+    };
+    (@entry $table:ident $key:tt ($($value:tt)+)) => {
+        $table.insert($crate::toml!(@key $key), $crate::toml!(@value $($value)+));
+    };
+    (@entry $table:ident $key:tt ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::toml!(@entry $table $key ($($value)* $next) $($rest)*)
+    };
+
+    // elements of an array, munched the same way
+    (@array $array:ident ()) => {};
+    (@array $array:ident ($($value:tt)+)) => {
+        $array.push($crate::toml!(@value $($value)+));
+    };
+    (@array $array:ident ($($value:tt)+) , $($rest:tt)*) => {
+        $array.push($crate::toml!(@value $($value)+));
+        $crate::toml!(@array $array () $($rest)*);
+    };
+    (@array $array:ident ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::toml!(@array $array ($($value)* $next) $($rest)*)
+    };
+
+    (@key $key:ident) => {
+        ::std::string::String::from(stringify!($key))
+    };
+    (@key $key:literal) => {
+        ::std::string::String::from($key)
+    };
+
+    (@value { $($body:tt)* }) => {
+        $crate::toml!($($body)*)
+    };
+    (@value [ $($elements:tt)* ]) => {{
+        #[allow(unused_mut)]
+        let mut array = $crate::Array::new();
+        $crate::toml!(@array array () $($elements)*);
+        $crate::Value::Array(array)
+    }};
+    (@value $($value:tt)+) => {
+        $crate::Value::from($($value)+)
+    };
+
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut table = $crate::Table::new();
+        $crate::toml!(@table table $($body)*);
+        $crate::Value::Table(table)
+    }};
+}
@@ -1,71 +1,181 @@
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
-use crate::lexer::{Context, Lexer, Posture, Token};
+use indexmap::map::Entry;
 
+use crate::lexer::{Context, Lexer, Posture, StringKind, Token};
+
+use crate::document::Document;
 use crate::error::{Error, Result};
-use crate::toml::{Table, Value};
+use crate::ser::quote_key_if_needed;
+use crate::toml::{Table, Value, ValueKind};
+
+/// An absolute path from the root of the document to a table or value.
+///
+/// Each segment is a single key, or the index of an element when the path
+/// passes through an array of tables. Keeping segments separate (rather than
+/// joining them with dots) means that a key such as `"google.com"` cannot be
+/// confused with the two keys `google` and `com`.
+pub type Path = Vec<String>;
+
+/// Options that control how documents are parsed.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Refuse input longer than this many bytes before parsing any of it.
+    pub max_input_bytes: Option<usize>,
+    /// Accept dotted keys made only of digits, such as `3.14 = "pi"`, which
+    /// the spec reads as the key `3` containing the key `14`. Turning this
+    /// off rejects them, since they are usually a float written on the
+    /// wrong side of the `=`. On by default.
+    pub numeric_bare_keys: bool,
+    /// Require the uppercase `T` separator and `Z` offset in date-times, as
+    /// strict RFC 3339 does, rather than also accepting `t` and `z`.
+    pub strict_date_times: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: None,
+            numeric_bare_keys: true,
+            strict_date_times: false,
+        }
+    }
+}
 
 pub struct Parser<'a> {
     lexer: Lexer<'a>,
     root: Value,
     current_table_key: Vec<String>,
-    predefined_tables: Vec<String>,
-    inlined_tables: Vec<String>,
-    inlined_arrays: Vec<String>,
+    predefined_tables: HashSet<Path>,
+    inlined_tables: Vec<Path>,
+    inlined_arrays: HashSet<Path>,
+    spans: Option<HashMap<Path, Range<usize>>>,
+    line_limit: Option<usize>,
+    numeric_bare_keys: bool,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(text: &'a str) -> Self {
         Self {
             lexer: Lexer::new(text),
-            root: Value::Table(HashMap::new()),
+            root: Value::Table(Table::new()),
             current_table_key: Vec::new(),
-            predefined_tables: Vec::new(),
+            predefined_tables: HashSet::new(),
             inlined_tables: Vec::new(),
-            inlined_arrays: Vec::new(),
+            inlined_arrays: HashSet::new(),
+            spans: None,
+            line_limit: None,
+            numeric_bare_keys: true,
         }
     }
 
     pub fn from_str(text: &'a str) -> Result<Value> {
         let mut parser = Parser::new(text);
-        parser.toml()
+        parser.toml().map_err(|err| parser.locate(err))
+    }
+
+    pub fn from_str_with(text: &'a str, options: &ParserOptions) -> Result<Value> {
+        if let Some(limit) = options.max_input_bytes {
+            if text.len() > limit {
+                return Err(Error::InputTooLarge {
+                    size: text.len(),
+                    limit,
+                });
+            }
+        }
+        let mut parser = Parser::new(text);
+        parser.numeric_bare_keys = options.numeric_bare_keys;
+        parser.lexer.strict_date_times = options.strict_date_times;
+        parser.toml().map_err(|err| parser.locate(err))
+    }
+
+    pub fn from_str_prefix_lines(text: &'a str, n: usize) -> Result<Value> {
+        let mut parser = Parser::new(text);
+        parser.line_limit = Some(n);
+        parser.toml().map_err(|err| parser.locate(err))
+    }
+
+    pub fn document(text: &'a str) -> Result<Document<'a>> {
+        let mut parser = Parser::new(text);
+        parser.spans = Some(HashMap::new());
+        let root = parser.toml().map_err(|err| parser.locate(err))?;
+        Ok(Document::new(text, root, parser.spans.unwrap_or_default()))
+    }
+
+    /// Attaches the position of the last token seen, and the line it is on,
+    /// to a bare parse error.
+    fn locate(&self, err: Error) -> Error {
+        match err {
+            Error::Parse => {
+                let position = self.lexer.token_position();
+                Error::Syntax {
+                    position,
+                    line: self.lexer.line_text(position.line).into(),
+                }
+            }
+            err => err,
+        }
     }
 
     fn toml(&mut self) -> Result<Value> {
-        while let Some(token) = self.lexer.peek(Context::default())? {
+        // each iteration consumes one logical line, including any newline
+        // that ends it; multiline values count as a single line
+        let mut lines = 0;
+        loop {
+            if self.line_limit.is_some_and(|limit| lines >= limit) {
+                break;
+            }
+            let Some(token) = self.lexer.peek(Context::default())? else {
+                break;
+            };
+            lines += 1;
             match token {
                 Token::Newline => {
                     self.lexer.next(Context::default())?;
                 }
-                Token::String(_) => {
-                    let (key, value) = self.key_value_pair()?;
+                Token::String(..) => {
+                    let (key, value, span) = match self.key_value_pair() {
+                        Ok(pair) => pair,
+                        Err(err @ Error::DuplicateKey(_)) => {
+                            let table_key = self.absolute_key(&self.current_table_key, &[])?;
+                            return Err(prefix_duplicate_key(err, &table_key));
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    let kind = value.kind();
                     self.require_newline_or_eof()?;
-                    let mut table = self.current_table_mut()?;
+                    let table = self.current_table_mut()?;
                     let subtable_key = &key[..key.len() - 1];
-                    let subtable = Self::find_or_create_subtable_mut(&mut table, subtable_key)?;
+                    let subtable = Self::find_or_create_subtable_mut(table, subtable_key)?;
                     let last_segment = key.last().unwrap();
                     if subtable.contains_key(last_segment) {
-                        return Err(Error::Parse);
+                        let mut path = self.absolute_key(&self.current_table_key, subtable_key)?;
+                        path.push(last_segment.clone());
+                        return Err(Error::DuplicateKey(join_key(&path)));
+                    }
+                    subtable.insert(last_segment.clone(), value);
+                    // a dotted key defines every table along it, so none of
+                    // them can be reopened by a header later on
+                    for len in 0..key.len() {
+                        let table_key = self.absolute_key(&self.current_table_key, &key[..len])?;
+                        self.predefined_tables.insert(table_key);
                     }
-                    subtable.insert(last_segment.clone(), value.clone());
-                    let absolute_key =
-                        self.absolute_key_string(&self.current_table_key, &key[..key.len() - 1])?;
-                    self.predefined_tables.push(absolute_key.clone());
-                    if self
-                        .inlined_tables
-                        .iter()
-                        .any(|table| format!("{absolute_key}.{last_segment}").starts_with(table))
-                    {
+                    let mut absolute_key =
+                        self.absolute_key(&self.current_table_key, &key[..key.len() - 1])?;
+                    absolute_key.push(last_segment.clone());
+                    if self.extends_inline_value(&absolute_key) {
                         return Err(Error::Parse);
                     }
-                    if matches!(value, Value::Table(_)) {
-                        self.inlined_tables
-                            .push(format!("{absolute_key}.{last_segment}"));
+                    if let Some(spans) = &mut self.spans {
+                        spans.insert(absolute_key.clone(), span);
                     }
-                    if matches!(value, Value::Array(_)) {
-                        self.inlined_arrays
-                            .push(format!("{absolute_key}.{last_segment}"));
+                    match kind {
+                        ValueKind::Table => self.inlined_tables.push(absolute_key),
+                        ValueKind::Array => {
+                            self.inlined_arrays.insert(absolute_key);
+                        }
+                        _ => {}
                     }
                 }
                 Token::LeftBracket => {
@@ -82,7 +192,7 @@ impl<'a> Parser<'a> {
                                     }
                                     Some(Value::Array(_)) => {
                                         let arr = table.get_mut(segment).unwrap().as_arr_mut();
-                                        arr.last_mut().unwrap().as_table_mut()
+                                        Self::last_table_mut(arr)?
                                     }
                                     Some(_) => return Err(Error::Parse),
                                     None => {
@@ -104,12 +214,9 @@ impl<'a> Parser<'a> {
                                 _ => return Err(Error::Parse),
                             }
 
-                            let absolute_key =
-                                self.absolute_key_string(&[], &key[..key.len() - 1])?;
-                            if self
-                                .inlined_arrays
-                                .contains(&format!("{absolute_key}.{last_segment}"))
-                            {
+                            let mut absolute_key = self.absolute_key(&[], &key[..key.len() - 1])?;
+                            absolute_key.push(last_segment.clone());
+                            if self.extends_inline_value(&absolute_key) {
                                 return Err(Error::Parse);
                             }
 
@@ -117,47 +224,111 @@ impl<'a> Parser<'a> {
                         }
                         _ => {
                             let key = self.table()?;
-                            let mut table = self.root.as_table_mut();
-                            Self::find_or_create_subtable_mut(&mut table, &key)?;
-                            let abs_key = self.absolute_key_string(&[], &key)?;
-                            if self.predefined_tables.contains(&abs_key) {
+                            let (last_segment, parent_key) = key.split_last().unwrap();
+                            let table = self.root.as_table_mut();
+                            let parent = Self::find_or_create_subtable_mut(table, parent_key)?;
+                            // `[servers]` cannot name an array of tables
+                            if let Some(Value::Array(_)) = parent.get(last_segment) {
+                                let mut path = self.absolute_key(&[], parent_key)?;
+                                path.push(last_segment.clone());
+                                return Err(Error::DuplicateKey(join_key(&path)));
+                            }
+                            Self::find_or_create_subtable_mut(
+                                parent,
+                                std::slice::from_ref(last_segment),
+                            )?;
+                            let absolute_key = self.absolute_key(&[], &key)?;
+                            if self.extends_inline_value(&absolute_key) {
                                 return Err(Error::Parse);
                             }
-                            self.predefined_tables.push(abs_key);
+                            if self.predefined_tables.contains(&absolute_key) {
+                                return Err(Error::DuplicateKey(join_key(&absolute_key)));
+                            }
+                            self.predefined_tables.insert(absolute_key);
                             self.current_table_key = key;
                         }
                     }
                 }
-                _ => return Err(Error::Parse),
+                token => {
+                    return Err(Error::UnexpectedToken {
+                        token: token.to_string(),
+                        context: "at top level",
+                        position: self.lexer.token_position(),
+                    })
+                }
             }
         }
-        Ok(self.root.clone())
+        Ok(std::mem::replace(
+            &mut self.root,
+            Value::Table(Table::new()),
+        ))
     }
 
-    fn key_value_pair(&mut self) -> Result<(Vec<String>, Value)> {
+    fn key_value_pair(&mut self) -> Result<(Vec<String>, Value, Range<usize>)> {
         let key = self.key()?;
+        // catch keys with a space in them that were not quoted
+        match self.lexer.peek(Context::default())? {
+            Some(Token::String(found, _)) => {
+                return Err(Error::ExpectedEquals {
+                    found,
+                    position: self.lexer.token_position(),
+                })
+            }
+            // a stray value, such as `"text"` or `42` on a line of its own
+            Some(Token::Newline) | None => {
+                return Err(Error::MissingEquals {
+                    key: join_key(&key),
+                    position: self.lexer.token_position(),
+                })
+            }
+            _ => {}
+        }
         self.require(Token::Equal)?;
-        let value = self.value()?;
-        Ok((key, value))
+        self.lexer.skip_whitespace();
+        let start = self.lexer.pos();
+        let value = self
+            .value()
+            .map_err(|err| prefix_duplicate_key(err, &key))?;
+        Ok((key, value, start..self.lexer.pos()))
     }
 
     fn key(&mut self) -> Result<Vec<String>> {
         let mut key = Vec::new();
+        let mut numeric = !self.numeric_bare_keys && self.peek_numeric_bare_key()?;
         let segment = self.require_string()?;
+        let position = self.lexer.token_position();
         key.push(segment);
         while let Some(Token::Dot) = self.lexer.peek(Context::default())? {
             self.require(Token::Dot)?;
+            numeric = numeric && self.peek_numeric_bare_key()?;
             let segment = self.require_string()?;
             key.push(segment);
         }
+        if numeric && key.len() > 1 {
+            return Err(Error::NumericKey {
+                key: key.join("."),
+                position,
+            });
+        }
         Ok(key)
     }
 
+    /// Checks whether the next token is a bare key made only of digits, such
+    /// as either half of `3.14`.
+    fn peek_numeric_bare_key(&mut self) -> Result<bool> {
+        Ok(matches!(
+            self.lexer.peek(Context::default())?,
+            Some(Token::String(segment, StringKind::Bare))
+                if segment.bytes().all(|b| b.is_ascii_digit())
+        ))
+    }
+
     fn value(&mut self) -> Result<Value> {
-        let mut context = Context::default();
-        context.posture = Some(Posture::Value);
+        let context = Context {
+            posture: Some(Posture::Value),
+        };
         let value = match self.lexer.peek(context.clone())? {
-            Some(Token::String(x)) => Value::String(x),
+            Some(Token::String(x, _)) => Value::String(x),
             Some(Token::Integer(x)) => Value::Integer(x),
             Some(Token::Float(x)) => Value::Float(x),
             Some(Token::Bool(x)) => Value::Bool(x),
@@ -167,7 +338,14 @@ impl<'a> Parser<'a> {
             Some(Token::LocalTime(x)) => Value::LocalTime(x),
             Some(Token::LeftBrace) => self.inline_table()?,
             Some(Token::LeftBracket) => self.array()?,
-            _ => return Err(Error::Parse),
+            Some(Token::Newline) | None => return Err(Error::MissingValue(self.lexer.position())),
+            Some(token) => {
+                return Err(Error::UnexpectedToken {
+                    token: token.to_string(),
+                    context: "in value position",
+                    position: self.lexer.token_position(),
+                })
+            }
         };
 
         if matches!(
@@ -191,31 +369,35 @@ impl<'a> Parser<'a> {
     fn inline_table(&mut self) -> Result<Value> {
         let mut inline_table = Table::new();
         self.require(Token::LeftBrace)?;
+        self.forbid_newline_in_inline_table()?;
 
         match self.lexer.peek(Context::default())? {
             Some(Token::RightBrace) => {}
-            Some(Token::String(_)) => {
-                let (key, value) = self.key_value_pair()?;
+            Some(Token::String(..)) => {
+                let (key, value, _) = self.key_value_pair()?;
                 let root = &mut inline_table;
                 let subtable_key = &key[..key.len() - 1];
                 let subtable = Self::find_or_create_subtable_mut(root, subtable_key)?;
                 let last_segment = key.last().unwrap();
                 if subtable.contains_key(last_segment) {
-                    return Err(Error::Parse);
+                    return Err(Error::DuplicateKey(join_key(&key)));
                 }
                 subtable.insert(last_segment.clone(), value);
+                self.forbid_newline_in_inline_table()?;
 
                 while let Some(Token::Comma) = self.lexer.peek(Context::default())? {
                     self.require(Token::Comma)?;
-                    let (key, value) = self.key_value_pair()?;
+                    self.forbid_newline_in_inline_table()?;
+                    let (key, value, _) = self.key_value_pair()?;
                     let root = &mut inline_table;
                     let subtable_key = &key[..key.len() - 1];
                     let subtable = Self::find_or_create_subtable_mut(root, subtable_key)?;
                     let last_segment = key.last().unwrap();
                     if subtable.contains_key(last_segment) {
-                        return Err(Error::Parse);
+                        return Err(Error::DuplicateKey(join_key(&key)));
                     }
                     subtable.insert(last_segment.clone(), value);
+                    self.forbid_newline_in_inline_table()?;
                 }
             }
             _ => return Err(Error::Parse),
@@ -225,15 +407,26 @@ impl<'a> Parser<'a> {
         Ok(Value::Table(inline_table))
     }
 
+    fn forbid_newline_in_inline_table(&mut self) -> Result<()> {
+        if let Some(Token::Newline) = self.lexer.peek(Context::default())? {
+            return Err(Error::NewlineInInlineTable(self.lexer.token_position()));
+        }
+        Ok(())
+    }
+
     fn array(&mut self) -> Result<Value> {
         let mut array = Vec::new();
         self.require(Token::LeftBracket)?;
+        let start = self.lexer.token_position();
+        self.skip_newlines()?;
 
         match self.lexer.peek(Context::default())? {
             Some(Token::RightBracket) => {}
+            None => return Err(Error::UnterminatedArray(start)),
             _ => {
-                self.skip_newlines()?;
-                let value = self.value()?;
+                let value = self
+                    .value()
+                    .map_err(|err| prefix_duplicate_key(err, &["0"]))?;
                 array.push(value);
                 self.skip_newlines()?;
                 while let Some(Token::Comma) = self.lexer.peek(Context::default())? {
@@ -241,8 +434,12 @@ impl<'a> Parser<'a> {
                     self.skip_newlines()?;
                     match self.lexer.peek(Context::default())? {
                         Some(Token::RightBracket) => break,
+                        None => return Err(Error::UnterminatedArray(start)),
                         _ => {
-                            let value = self.value()?;
+                            let index = array.len().to_string();
+                            let value = self
+                                .value()
+                                .map_err(|err| prefix_duplicate_key(err, &[index]))?;
                             array.push(value);
                             self.skip_newlines()?;
                         }
@@ -251,8 +448,11 @@ impl<'a> Parser<'a> {
             }
         }
 
-        self.require(Token::RightBracket)?;
-        Ok(Value::Array(array))
+        match self.lexer.next(Context::default())? {
+            Some(Token::RightBracket) => Ok(Value::Array(array)),
+            None => Err(Error::UnterminatedArray(start)),
+            Some(_) => Err(Error::Parse),
+        }
     }
 
     fn table(&mut self) -> Result<Vec<String>> {
@@ -283,7 +483,7 @@ impl<'a> Parser<'a> {
                 Entry::Vacant(vacancy) => vacancy.insert(Value::Table(Table::new())).as_table_mut(),
                 Entry::Occupied(occupant) => match occupant.into_mut() {
                     Value::Table(table) => table,
-                    Value::Array(array) => array.last_mut().unwrap().as_table_mut(),
+                    Value::Array(array) => Self::last_table_mut(array)?,
                     _ => return Err(Error::Parse),
                 },
             }
@@ -298,7 +498,7 @@ impl<'a> Parser<'a> {
                 Some(Value::Table(_)) => table.get_mut(segment).unwrap().as_table_mut(),
                 Some(Value::Array(_)) => {
                     let arr = table.get_mut(segment).unwrap().as_arr_mut();
-                    arr.last_mut().unwrap().as_table_mut()
+                    Self::last_table_mut(arr)?
                 }
                 _ => return Err(Error::Parse),
             }
@@ -306,6 +506,16 @@ impl<'a> Parser<'a> {
         Ok(table)
     }
 
+    /// Returns the table most recently added to an array of tables, failing
+    /// if the array is empty or ends in some other value, as a static array
+    /// can.
+    fn last_table_mut(array: &mut [Value]) -> Result<&mut Table> {
+        match array.last_mut() {
+            Some(Value::Table(table)) => Ok(table),
+            _ => Err(Error::Parse),
+        }
+    }
+
     fn require(&mut self, token: Token) -> Result<()> {
         match self.lexer.next(Context::default())? {
             Some(actual) if actual == token => Ok(()),
@@ -315,7 +525,10 @@ impl<'a> Parser<'a> {
 
     fn require_string(&mut self) -> Result<String> {
         match self.lexer.next(Context::default())? {
-            Some(Token::String(string)) => Ok(string),
+            Some(Token::String(_, StringKind::MultilineBasic | StringKind::MultilineLiteral)) => {
+                Err(Error::Parse)
+            }
+            Some(Token::String(string, _)) => Ok(string),
             _ => Err(Error::Parse),
         }
     }
@@ -334,26 +547,86 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    fn absolute_key_string(&self, base_key: &[String], rel_key: &[String]) -> Result<String> {
-        let mut string = String::new();
+    fn absolute_key(&self, base_key: &[String], rel_key: &[String]) -> Result<Path> {
+        let mut path = Path::new();
         let mut table = self.root.as_table();
-        for segment in base_key
-            .iter()
-            .chain(rel_key.iter())
-            .collect::<Vec<&String>>()
-        {
+        for segment in base_key.iter().chain(rel_key.iter()) {
             table = match table.get(segment) {
                 Some(Value::Table(table)) => {
-                    string.push_str(&format!(".{segment}"));
+                    path.push(segment.clone());
                     table
                 }
-                Some(Value::Array(array)) => {
-                    string.push_str(&format!(".{segment}.{}", array.len() - 1));
-                    array.last().unwrap().as_table()
-                }
+                Some(Value::Array(array)) => match array.last() {
+                    Some(Value::Table(table)) => {
+                        path.push(segment.clone());
+                        path.push((array.len() - 1).to_string());
+                        table
+                    }
+                    _ => return Err(Error::Parse),
+                },
                 _ => return Err(Error::Parse),
             }
         }
-        Ok(string)
+        Ok(path)
+    }
+
+    /// Returns true if the path is, or is inside, an inline table or a static
+    /// array. Both are complete once defined and cannot be extended.
+    fn extends_inline_value(&self, path: &[String]) -> bool {
+        self.inlined_tables
+            .iter()
+            .any(|table| path.starts_with(table))
+            || (1..=path.len()).any(|len| self.inlined_arrays.contains(&path[..len]))
+    }
+}
+
+/// Joins the segments of a key with dots, quoting any that are not bare.
+fn join_key<S: AsRef<str>>(key: &[S]) -> String {
+    let segments: Vec<String> = key
+        .iter()
+        .map(|segment| quote_key_if_needed(segment.as_ref()))
+        .collect();
+    segments.join(".")
+}
+
+/// Puts `prefix` in front of the path of a duplicate key reported from
+/// inside a value, so the path is relative to the enclosing table.
+fn prefix_duplicate_key<S: AsRef<str>>(err: Error, prefix: &[S]) -> Error {
+    match err {
+        Error::DuplicateKey(path) if !prefix.is_empty() => {
+            Error::DuplicateKey(format!("{}.{path}", join_key(prefix)))
+        }
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Result,
+        toml::{Table, Value},
+    };
+
+    use super::Parser;
+
+    #[test]
+    fn toml_moves_root_out() -> Result<()> {
+        let text = r#"
+title = "TOML Example"
+ports = [8000, 8001]
+
+[owner.address]
+city = "San Francisco"
+
+[[servers]]
+host = "alpha"
+"#;
+        let mut parser = Parser::new(text);
+        let root = parser.toml()?;
+        assert_eq!(root["title"].as_str(), "TOML Example");
+        assert_eq!(root["owner"]["address"]["city"].as_str(), "San Francisco");
+        assert_eq!(root["servers"][0]["host"].as_str(), "alpha");
+        assert_eq!(parser.root, Value::Table(Table::new()));
+        Ok(())
     }
 }
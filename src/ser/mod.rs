@@ -0,0 +1,136 @@
+//! Writing values as TOML text.
+
+use std::fmt::Write;
+
+use crate::error::{Error, Result};
+use crate::escape::escape_basic_string;
+use crate::toml::{Table, Value};
+
+#[cfg(feature = "serde")]
+mod value;
+
+#[cfg(feature = "serde")]
+pub use value::to_string_serde;
+
+/// Options that control how documents are written.
+#[derive(Debug, Default, Clone)]
+pub struct SerializerOptions {
+    /// Write tables with fewer than this many entries, all of them scalars,
+    /// as inline tables instead of under their own header.
+    pub prefer_inline_threshold: Option<usize>,
+}
+
+/// Writes a table as a TOML document.
+///
+/// Keys are written in sorted order. Within each table, key-value pairs come
+/// first, followed by subtables and arrays of tables under their own headers.
+/// Headers are omitted for tables that only contain other tables.
+pub fn to_string(value: &Value) -> Result<String> {
+    to_string_with(value, &SerializerOptions::default())
+}
+
+/// Writes a table as a TOML document, as [`to_string`] does, but with the
+/// given options.
+pub fn to_string_with(value: &Value, options: &SerializerOptions) -> Result<String> {
+    if !matches!(value, Value::Table(_)) {
+        return Err(Error::UnexpectedType {
+            expected: "table",
+            found: value.type_name(),
+        });
+    }
+    let mut text = String::new();
+    write_table(&mut text, &mut Vec::new(), value, options);
+    Ok(text)
+}
+
+fn write_table(
+    text: &mut String,
+    path: &mut Vec<String>,
+    table: &Value,
+    options: &SerializerOptions,
+) {
+    let entries = table.sorted_entries();
+    for (key, value) in &entries {
+        if !is_section(value, options) {
+            writeln!(text, "{} = {value}", quote_key_if_needed(key)).unwrap();
+        }
+    }
+    for (key, value) in &entries {
+        if !is_section(value, options) {
+            continue;
+        }
+        path.push(quote_key_if_needed(key));
+        match value {
+            Value::Table(table)
+                if table.is_empty() || !table.values().all(|value| is_section(value, options)) =>
+            {
+                write_header(text, &format!("[{}]", path.join(".")));
+                write_table(text, path, value, options);
+            }
+            Value::Table(_) => write_table(text, path, value, options),
+            Value::Array(array) => {
+                for table in array {
+                    write_header(text, &format!("[[{}]]", path.join(".")));
+                    write_table(text, path, table, options);
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+fn write_header(text: &mut String, header: &str) {
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(header);
+    text.push('\n');
+}
+
+/// Returns true if the value is written under its own header rather than as
+/// a key-value pair: a table that is not written inline, or a non-empty array
+/// made up only of tables.
+fn is_section(value: &Value, options: &SerializerOptions) -> bool {
+    match value {
+        Value::Table(table) => !is_inline(table, options),
+        Value::Array(array) => {
+            !array.is_empty() && array.iter().all(|value| matches!(value, Value::Table(_)))
+        }
+        _ => false,
+    }
+}
+
+fn is_inline(table: &Table, options: &SerializerOptions) -> bool {
+    options.prefer_inline_threshold.is_some_and(|threshold| {
+        table.len() < threshold
+            && table
+                .values()
+                .all(|value| !matches!(value, Value::Array(_) | Value::Table(_)))
+    })
+}
+
+/// Returns `key` unchanged if it is a valid bare key, or as a quoted basic
+/// string otherwise.
+///
+/// ```rust
+/// use toml::ser::quote_key_if_needed;
+///
+/// assert_eq!(quote_key_if_needed("bare_key"), "bare_key");
+/// assert_eq!(quote_key_if_needed("google.com"), r#""google.com""#);
+/// ```
+pub fn quote_key_if_needed(key: &str) -> String {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_bare {
+        key.into()
+    } else {
+        quote_basic_string(key)
+    }
+}
+
+pub(crate) fn quote_basic_string(string: &str) -> String {
+    format!("\"{}\"", escape_basic_string(string))
+}
@@ -1,27 +1,50 @@
 use std::{
     collections::HashMap,
+    fmt::{self, Display, Write},
     ops::{Index, IndexMut},
+    slice, vec,
 };
 
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use indexmap::{map, IndexMap};
+use lazy_static::lazy_static;
 
-pub type Table = HashMap<String, Value>;
+use crate::datetime::{LocalDate, LocalDateTime, LocalTime, OffsetDateTime};
+use crate::error::{Error, Result};
+use crate::ser;
+
+/// A table's entries, kept in the order they were defined.
+pub type Table = IndexMap<String, Value>;
 pub type Array = Vec<Value>;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(PartialEq, Clone)]
 pub enum Value {
     String(String),
     Integer(i64),
     Float(f64),
     Bool(bool),
-    OffsetDateTime(DateTime<FixedOffset>),
-    LocalDateTime(NaiveDateTime),
-    LocalDate(NaiveDate),
-    LocalTime(NaiveTime),
+    OffsetDateTime(OffsetDateTime),
+    LocalDateTime(LocalDateTime),
+    LocalDate(LocalDate),
+    LocalTime(LocalTime),
     Array(Array),
     Table(Table),
 }
 
+/// The type of a [`Value`], without its contents.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ValueKind {
+    String,
+    Integer,
+    Float,
+    Bool,
+    OffsetDateTime,
+    LocalDateTime,
+    LocalDate,
+    LocalTime,
+    Array,
+    Table,
+}
+
 impl Value {
     pub fn as_str(&self) -> &str {
         match self {
@@ -44,6 +67,55 @@ impl Value {
         }
     }
 
+    pub fn as_u8(&self) -> Result<u8> {
+        self.as_int_checked("u8")
+    }
+
+    pub fn as_u16(&self) -> Result<u16> {
+        self.as_int_checked("u16")
+    }
+
+    pub fn as_u32(&self) -> Result<u32> {
+        self.as_int_checked("u32")
+    }
+
+    pub fn as_u64(&self) -> Result<u64> {
+        self.as_int_checked("u64")
+    }
+
+    pub fn as_usize(&self) -> Result<usize> {
+        self.as_int_checked("usize")
+    }
+
+    pub fn as_i32(&self) -> Result<i32> {
+        self.as_int_checked("i32")
+    }
+
+    /// Converts an integer value to a narrower type, failing if the value is
+    /// not an integer or does not fit, such as a negative port number.
+    fn as_int_checked<T: TryFrom<i64>>(&self, target: &'static str) -> Result<T> {
+        match self {
+            Value::Integer(int) => T::try_from(*int).map_err(|_| Error::OutOfRange {
+                value: *int,
+                target,
+            }),
+            _ => Err(Error::UnexpectedType {
+                expected: "integer",
+                found: self.type_name(),
+            }),
+        }
+    }
+
+    /// Returns an integer or float as an `f64`. `inf` and `nan` are always
+    /// floats, so this is the way to accept them alongside integers.
+    pub fn as_number(&self) -> f64 {
+        match self {
+            Value::Integer(int) => *int as f64,
+            Value::Float(float) => *float,
+            _ => panic!("not a number value"),
+        }
+    }
+
     pub fn as_bool(&self) -> bool {
         match self {
             Value::Bool(bool) => *bool,
@@ -51,48 +123,192 @@ impl Value {
         }
     }
 
-    pub fn as_offset_date_time(&self) -> DateTime<FixedOffset> {
+    #[cfg(feature = "chrono")]
+    pub fn as_offset_date_time(&self) -> OffsetDateTime {
         match self {
             Value::OffsetDateTime(x) => *x,
             _ => panic!("wrong type"),
         }
     }
 
-    pub fn as_local_date_time(&self) -> NaiveDateTime {
+    #[cfg(feature = "chrono")]
+    pub fn as_local_date_time(&self) -> LocalDateTime {
         match self {
             Value::LocalDateTime(x) => *x,
             _ => panic!("wrong type"),
         }
     }
 
-    pub fn as_local_date(&self) -> NaiveDate {
+    #[cfg(feature = "chrono")]
+    pub fn as_local_date(&self) -> LocalDate {
         match self {
             Value::LocalDate(x) => *x,
             _ => panic!("wrong type"),
         }
     }
 
-    pub fn as_local_time(&self) -> NaiveTime {
+    #[cfg(feature = "chrono")]
+    pub fn as_local_time(&self) -> LocalTime {
         match self {
             Value::LocalTime(x) => *x,
             _ => panic!("wrong type"),
         }
     }
 
-    pub fn as_table(&self) -> &HashMap<String, Value> {
+    /// Returns an offset date-time as is, or a local date-time taken to be in
+    /// UTC. Returns `None` for any other value.
+    #[cfg(feature = "chrono")]
+    pub fn as_timestamp(&self) -> Option<OffsetDateTime> {
+        self.as_timestamp_with_offset(chrono::FixedOffset::east_opt(0).unwrap())
+    }
+
+    /// Returns an offset date-time as is, or a local date-time taken to be at
+    /// `default_offset`. Returns `None` for any other value.
+    #[cfg(feature = "chrono")]
+    pub fn as_timestamp_with_offset(
+        &self,
+        default_offset: chrono::FixedOffset,
+    ) -> Option<OffsetDateTime> {
+        match self {
+            Value::OffsetDateTime(x) => Some(*x),
+            Value::LocalDateTime(x) => x.and_local_timezone(default_offset).single(),
+            _ => None,
+        }
+    }
+
+    /// Returns the year of a date or date-time, or `None` for any other
+    /// value. Works the same with or without the `chrono` feature.
+    pub fn year(&self) -> Option<u32> {
+        self.date_parts().map(|(year, _, _)| year)
+    }
+
+    /// Returns the month of a date or date-time, from 1 to 12.
+    pub fn month(&self) -> Option<u32> {
+        self.date_parts().map(|(_, month, _)| month)
+    }
+
+    /// Returns the day of the month of a date or date-time, from 1 to 31.
+    pub fn day(&self) -> Option<u32> {
+        self.date_parts().map(|(_, _, day)| day)
+    }
+
+    /// Returns the hour of a time or date-time, from 0 to 23.
+    pub fn hour(&self) -> Option<u32> {
+        self.time_parts().map(|(hour, _, _)| hour)
+    }
+
+    /// Returns the minute of a time or date-time, from 0 to 59.
+    pub fn minute(&self) -> Option<u32> {
+        self.time_parts().map(|(_, minute, _)| minute)
+    }
+
+    /// Returns the whole seconds of a time or date-time, from 0 to 60.
+    pub fn second(&self) -> Option<u32> {
+        self.time_parts().map(|(_, _, second)| second)
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_parts(&self) -> Option<(u32, u32, u32)> {
+        use chrono::Datelike;
+        let date = match self {
+            Value::OffsetDateTime(x) => x.date_naive(),
+            Value::LocalDateTime(x) => x.date(),
+            Value::LocalDate(x) => *x,
+            _ => return None,
+        };
+        Some((date.year() as u32, date.month(), date.day()))
+    }
+
+    #[cfg(feature = "chrono")]
+    fn time_parts(&self) -> Option<(u32, u32, u32)> {
+        use chrono::Timelike;
+        let time = match self {
+            Value::OffsetDateTime(x) => x.time(),
+            Value::LocalDateTime(x) => x.time(),
+            Value::LocalTime(x) => *x,
+            _ => return None,
+        };
+        // chrono counts a leap second as extra nanoseconds on second 59
+        let leap = u32::from(time.nanosecond() >= 1_000_000_000);
+        Some((time.hour(), time.minute(), time.second() + leap))
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    fn date_parts(&self) -> Option<(u32, u32, u32)> {
+        let text = match self {
+            Value::OffsetDateTime(x) | Value::LocalDateTime(x) | Value::LocalDate(x) => x.as_str(),
+            _ => return None,
+        };
+        let part = |range: std::ops::Range<usize>| -> Option<u32> { text.get(range)?.parse().ok() };
+        Some((part(0..4)?, part(5..7)?, part(8..10)?))
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    fn time_parts(&self) -> Option<(u32, u32, u32)> {
+        let text = match self {
+            Value::OffsetDateTime(x) | Value::LocalDateTime(x) => x.as_str().get(11..)?,
+            Value::LocalTime(x) => x.as_str(),
+            _ => return None,
+        };
+        let part = |range: std::ops::Range<usize>| -> Option<u32> { text.get(range)?.parse().ok() };
+        Some((part(0..2)?, part(3..5)?, part(6..8)?))
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    pub fn as_offset_date_time(&self) -> &OffsetDateTime {
+        match self {
+            Value::OffsetDateTime(x) => x,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    pub fn as_local_date_time(&self) -> &LocalDateTime {
+        match self {
+            Value::LocalDateTime(x) => x,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    pub fn as_local_date(&self) -> &LocalDate {
+        match self {
+            Value::LocalDate(x) => x,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    pub fn as_local_time(&self) -> &LocalTime {
+        match self {
+            Value::LocalTime(x) => x,
+            _ => panic!("wrong type"),
+        }
+    }
+
+    pub fn as_table(&self) -> &Table {
         match self {
             Value::Table(table) => table,
             _ => panic!("not a table value"),
         }
     }
 
-    pub fn as_table_mut(&mut self) -> &mut HashMap<String, Value> {
+    pub fn as_table_mut(&mut self) -> &mut Table {
         match self {
             Value::Table(table) => table,
             _ => panic!("not a table value"),
         }
     }
 
+    /// Returns the table, or `None` if the value is not a table. Unlike
+    /// [`Value::as_table`], this does not panic.
+    pub fn as_map(&self) -> Option<&Table> {
+        match self {
+            Value::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
     pub fn as_arr(&self) -> &[Value] {
         match self {
             Value::Array(array) => array,
@@ -107,6 +323,53 @@ impl Value {
         }
     }
 
+    /// Returns the first element of an array, or `None` if the array is
+    /// empty or the value is not an array.
+    pub fn first(&self) -> Option<&Value> {
+        match self {
+            Value::Array(array) => array.first(),
+            _ => None,
+        }
+    }
+
+    /// Returns the last element of an array, or `None` if the array is empty
+    /// or the value is not an array.
+    pub fn last(&self) -> Option<&Value> {
+        match self {
+            Value::Array(array) => array.last(),
+            _ => None,
+        }
+    }
+
+    /// Removes repeated elements from an array, keeping the first occurrence
+    /// of each. Elements are compared with `==`, so `1` and `1.0` are
+    /// distinct, as are `nan` floats. Does nothing if the value is not an
+    /// array.
+    pub fn dedup_array(&mut self) {
+        if let Value::Array(array) = self {
+            let mut unique: Array = Vec::with_capacity(array.len());
+            for value in array.drain(..) {
+                if !unique.contains(&value) {
+                    unique.push(value);
+                }
+            }
+            *array = unique;
+        }
+    }
+
+    /// Iterates over the tables in an array, such as the elements of an
+    /// array of tables, skipping any element that is not a table. Yields
+    /// nothing if the value is not an array.
+    pub fn tables(&self) -> impl Iterator<Item = &Value> {
+        let array = match self {
+            Value::Array(array) => array.as_slice(),
+            _ => &[],
+        };
+        array
+            .iter()
+            .filter(|value| matches!(value, Value::Table(_)))
+    }
+
     pub fn insert(&mut self, key: String, value: Value) {
         match self {
             Value::Table(table) => {
@@ -115,6 +378,731 @@ impl Value {
             _ => panic!("not a table value"),
         }
     }
+
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::String(_) => ValueKind::String,
+            Value::Integer(_) => ValueKind::Integer,
+            Value::Float(_) => ValueKind::Float,
+            Value::Bool(_) => ValueKind::Bool,
+            Value::OffsetDateTime(_) => ValueKind::OffsetDateTime,
+            Value::LocalDateTime(_) => ValueKind::LocalDateTime,
+            Value::LocalDate(_) => ValueKind::LocalDate,
+            Value::LocalTime(_) => ValueKind::LocalTime,
+            Value::Array(_) => ValueKind::Array,
+            Value::Table(_) => ValueKind::Table,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "boolean",
+            Value::OffsetDateTime(_) => "offset date-time",
+            Value::LocalDateTime(_) => "local date-time",
+            Value::LocalDate(_) => "local date",
+            Value::LocalTime(_) => "local time",
+            Value::Array(_) => "array",
+            Value::Table(_) => "table",
+        }
+    }
+
+    /// Looks up a value by a dotted path such as `servers.0.host`, where
+    /// numeric segments index into arrays.
+    pub fn pointer(&self, path: &str) -> Option<&Value> {
+        self.get_path(path.split('.'))
+    }
+
+    /// Looks up a value by explicit path segments, so keys containing dots
+    /// can be reached. Numeric segments index into arrays.
+    pub fn get_path<'k>(&self, segments: impl IntoIterator<Item = &'k str>) -> Option<&Value> {
+        let mut value = self;
+        for segment in segments {
+            value = match value {
+                Value::Table(table) => table.get(segment)?,
+                Value::Array(array) => array.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// Sets the value at a dotted path such as `server.tls.port`, creating
+    /// any missing tables along the way. Numeric segments index into existing
+    /// array elements, as they do for [`Value::pointer`].
+    pub fn set_path(&mut self, path: &str, value: Value) -> Result<()> {
+        let segments: Vec<&str> = path.split('.').collect();
+        let mut current = self;
+        for (i, segment) in segments.iter().enumerate() {
+            let is_last = i == segments.len() - 1;
+            current = match current {
+                Value::Table(table) => match table.entry(segment.to_string()) {
+                    map::Entry::Occupied(entry) if is_last => {
+                        *entry.into_mut() = value;
+                        return Ok(());
+                    }
+                    map::Entry::Vacant(entry) if is_last => {
+                        entry.insert(value);
+                        return Ok(());
+                    }
+                    entry => entry.or_insert_with(|| Value::Table(Table::new())),
+                },
+                Value::Array(array) => {
+                    let element = segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| array.get_mut(index))
+                        .ok_or_else(|| Error::MissingKey(segments[..=i].join(".")))?;
+                    if is_last {
+                        *element = value;
+                        return Ok(());
+                    }
+                    element
+                }
+                other => {
+                    return Err(Error::WrongType {
+                        path: segments[..i].join("."),
+                        expected: "table",
+                        found: other.type_name(),
+                    })
+                }
+            };
+        }
+        unreachable!("split always yields at least one segment")
+    }
+
+    pub fn require_str(&self, path: &str) -> Result<&str> {
+        Ok(self.require(path, "string")?.as_str())
+    }
+
+    pub fn require_int(&self, path: &str) -> Result<i64> {
+        Ok(self.require(path, "integer")?.as_int())
+    }
+
+    pub fn require_float(&self, path: &str) -> Result<f64> {
+        Ok(self.require(path, "float")?.as_float())
+    }
+
+    pub fn require_bool(&self, path: &str) -> Result<bool> {
+        Ok(self.require(path, "boolean")?.as_bool())
+    }
+
+    pub fn require_arr(&self, path: &str) -> Result<&[Value]> {
+        Ok(self.require(path, "array")?.as_arr())
+    }
+
+    pub fn require_table(&self, path: &str) -> Result<&Table> {
+        Ok(self.require(path, "table")?.as_table())
+    }
+
+    fn require(&self, path: &str, expected: &'static str) -> Result<&Value> {
+        let value = self
+            .pointer(path)
+            .ok_or_else(|| Error::MissingKey(path.into()))?;
+        if value.type_name() != expected {
+            return Err(Error::WrongType {
+                path: path.into(),
+                expected,
+                found: value.type_name(),
+            });
+        }
+        Ok(value)
+    }
+
+    /// Returns the table at `key`, or an empty table if the key is missing or
+    /// holds some other type of value.
+    pub fn get_table_or_empty(&self, key: &str) -> &Value {
+        lazy_static! {
+            static ref EMPTY: Value = Value::Table(Table::new());
+        }
+        match self {
+            Value::Table(table) => match table.get(key) {
+                Some(value @ Value::Table(_)) => value,
+                _ => &EMPTY,
+            },
+            _ => &EMPTY,
+        }
+    }
+
+    /// Returns the value at `key`, or `default` if the key is missing or this
+    /// is not a table.
+    pub fn get_or<'a>(&'a self, key: &str, default: &'a Value) -> &'a Value {
+        match self {
+            Value::Table(table) => table.get(key).unwrap_or(default),
+            _ => default,
+        }
+    }
+
+    /// Starts a lookup that tolerates missing keys, so a deep optional path
+    /// can be written as `value.at("a").at("b").get()` without panicking.
+    pub fn at(&self, key: &str) -> ValueRef<'_> {
+        ValueRef(Some(self)).at(key)
+    }
+
+    /// Looks up a table entry by key, ignoring ASCII case. An exact match is
+    /// preferred; otherwise, if several keys differ only in case, the first
+    /// one defined wins. Returns `None` for values that are not tables.
+    pub fn get_ci(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Table(table) => table.get(key).or_else(|| {
+                table
+                    .iter()
+                    .find(|(candidate, _)| candidate.eq_ignore_ascii_case(key))
+                    .map(|(_, value)| value)
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys of a table in definition order, such as the sections
+    /// of a document, or an empty list if the value is not a table.
+    pub fn top_level_keys(&self) -> Vec<&str> {
+        match self {
+            Value::Table(table) => table.keys().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the key of the table entry at `index`, in definition order.
+    pub fn key_at(&self, index: usize) -> Option<&str> {
+        match self {
+            Value::Table(table) => table.get_index(index).map(|(key, _)| key.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the table entry at `index`, in definition order.
+    pub fn value_at(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Table(table) => table.get_index(index).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Iterates over the entries of a table in definition order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a table.
+    pub fn entries(&self) -> map::Iter<'_, String, Value> {
+        self.as_table().iter()
+    }
+
+    /// Consumes a table and iterates over its entries in definition order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a table.
+    pub fn into_entries(self) -> map::IntoIter<String, Value> {
+        match self {
+            Value::Table(table) => table.into_iter(),
+            _ => panic!("not a table value"),
+        }
+    }
+
+    /// Returns the entries of a table sorted by key, for output that should
+    /// not depend on definition order, or an empty list if the value is not
+    /// a table.
+    pub fn sorted_entries(&self) -> Vec<(&String, &Value)> {
+        let Value::Table(table) = self else {
+            return Vec::new();
+        };
+        let mut entries: Vec<_> = table.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        entries
+    }
+
+    pub fn append_array(&mut self, other: Vec<Value>) -> Result<()> {
+        match self {
+            Value::Array(array) => {
+                array.extend(other);
+                Ok(())
+            }
+            _ => Err(Error::UnexpectedType {
+                expected: "array",
+                found: self.type_name(),
+            }),
+        }
+    }
+
+    /// Calls `f` on each element of an array, in order, so elements can be
+    /// rewritten in place.
+    pub fn map_array(&mut self, f: impl FnMut(&mut Value)) -> Result<()> {
+        match self {
+            Value::Array(array) => {
+                array.iter_mut().for_each(f);
+                Ok(())
+            }
+            _ => Err(Error::UnexpectedType {
+                expected: "array",
+                found: self.type_name(),
+            }),
+        }
+    }
+
+    /// Returns whether every element of an array has the same type, or `None`
+    /// if the value is not an array. Integers and floats count as different
+    /// types, and an empty array is homogeneous.
+    pub fn is_homogeneous_array(&self) -> Option<bool> {
+        match self {
+            Value::Array(array) => {
+                Some(array.windows(2).all(|pair| {
+                    std::mem::discriminant(&pair[0]) == std::mem::discriminant(&pair[1])
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of scalar values, counting every element of arrays
+    /// and tables. Empty arrays and tables contribute nothing.
+    pub fn count_leaves(&self) -> usize {
+        match self {
+            Value::Array(array) => array.iter().map(Value::count_leaves).sum(),
+            Value::Table(table) => table.values().map(Value::count_leaves).sum(),
+            _ => 1,
+        }
+    }
+
+    /// Counts this value and every value nested in it by their
+    /// [`type_name`](Value::type_name). A document's root table is counted
+    /// too, as are the tables in an array of tables.
+    pub fn type_histogram(&self) -> HashMap<&'static str, usize> {
+        let mut histogram = HashMap::new();
+        self.count_types(&mut histogram);
+        histogram
+    }
+
+    fn count_types(&self, histogram: &mut HashMap<&'static str, usize>) {
+        *histogram.entry(self.type_name()).or_default() += 1;
+        match self {
+            Value::Array(array) => array.iter().for_each(|value| value.count_types(histogram)),
+            Value::Table(table) => table
+                .values()
+                .for_each(|value| value.count_types(histogram)),
+            _ => {}
+        }
+    }
+
+    /// Returns the deepest nesting of arrays and tables. Scalars have a depth
+    /// of 0 and a table of scalars, such as a flat document, has a depth of 1.
+    pub fn max_depth(&self) -> usize {
+        let children = match self {
+            Value::Array(array) => array.iter().map(Value::max_depth).max(),
+            Value::Table(table) => table.values().map(Value::max_depth).max(),
+            _ => return 0,
+        };
+        1 + children.unwrap_or(0)
+    }
+
+    /// Returns every value that is not a table, paired with its dotted path
+    /// from this value, in definition order. Arrays are returned whole, and
+    /// empty tables contribute nothing.
+    pub fn flatten(&self) -> Vec<(String, &Value)> {
+        let mut leaves = Vec::new();
+        self.flatten_into(&mut String::new(), &mut leaves);
+        leaves
+    }
+
+    fn flatten_into<'a>(&'a self, path: &mut String, leaves: &mut Vec<(String, &'a Value)>) {
+        match self {
+            Value::Table(table) => {
+                for (key, value) in table {
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    value.flatten_into(path, leaves);
+                    path.truncate(len);
+                }
+            }
+            _ => leaves.push((path.clone(), self)),
+        }
+    }
+
+    /// Yields the same pairs as [`Value::flatten`], in the same order, but
+    /// lazily, so a large document can be streamed without collecting every
+    /// leaf first.
+    pub fn leaves(&self) -> impl Iterator<Item = (String, &Value)> {
+        let mut stack = vec![(String::new(), self)];
+        std::iter::from_fn(move || {
+            while let Some((path, value)) = stack.pop() {
+                let Value::Table(table) = value else {
+                    return Some((path, value));
+                };
+                for (key, value) in table.iter().rev() {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    stack.push((path, value));
+                }
+            }
+            None
+        })
+    }
+
+    /// Flattens the document into environment variables named
+    /// `PREFIX_A_B_C`. Names are uppercased, with any character other than a
+    /// letter or digit replaced by `_`. Strings are exported as they are;
+    /// every other value, arrays included, is written in TOML syntax.
+    pub fn to_env_vars(&self, prefix: &str) -> Vec<(String, String)> {
+        self.flatten()
+            .into_iter()
+            .map(|(path, value)| {
+                let name = if prefix.is_empty() {
+                    path
+                } else {
+                    format!("{prefix}_{path}")
+                };
+                let name = name
+                    .chars()
+                    .map(|c| match c {
+                        c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+                        _ => '_',
+                    })
+                    .collect();
+                let value = match value {
+                    Value::String(string) => string.clone(),
+                    value => value.to_string(),
+                };
+                (name, value)
+            })
+            .collect()
+    }
+
+    /// Shortens every string longer than `max_len` characters, here and in
+    /// any nested arrays and tables, to its first `max_len` characters
+    /// followed by `...`. Useful for logging large documents.
+    pub fn truncate_strings(&mut self, max_len: usize) {
+        match self {
+            Value::String(string) => {
+                if let Some((end, _)) = string.char_indices().nth(max_len) {
+                    string.truncate(end);
+                    string.push_str("...");
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    value.truncate_strings(max_len);
+                }
+            }
+            Value::Table(table) => {
+                for value in table.values_mut() {
+                    value.truncate_strings(max_len);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Converts the line endings of every string, here and in any nested
+    /// arrays and tables, to `to`. Both `\n` and `\r\n` are converted; a lone
+    /// `\r` is left alone.
+    pub fn normalize_line_endings(&mut self, to: LineEnding) {
+        match self {
+            Value::String(string) if string.contains('\n') => {
+                let lf = string.replace("\r\n", "\n");
+                *string = match to {
+                    LineEnding::Lf => lf,
+                    LineEnding::CrLf => lf.replace('\n', "\r\n"),
+                };
+            }
+            Value::Array(array) => {
+                for value in array {
+                    value.normalize_line_endings(to);
+                }
+            }
+            Value::Table(table) => {
+                for value in table.values_mut() {
+                    value.normalize_line_endings(to);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Rewrites every `nan` and `inf` float, here and in any nested arrays
+    /// and tables, according to `policy`, since JSON has no way to represent
+    /// them. Finite floats are left alone, as is a bare float under
+    /// [`NanPolicy::Null`], which has no table or array to be removed from.
+    pub fn sanitize_for_json(&mut self, policy: NanPolicy) -> Result<()> {
+        self.sanitize_at(&mut String::new(), policy)?;
+        Ok(())
+    }
+
+    /// Returns whether the value should be dropped from its parent.
+    fn sanitize_at(&mut self, path: &mut String, policy: NanPolicy) -> Result<bool> {
+        match self {
+            Value::Float(float) if !float.is_finite() => match policy {
+                NanPolicy::Null => Ok(true),
+                NanPolicy::Error => Err(Error::Custom(format!(
+                    "`{self}` at `{path}` cannot be represented in JSON"
+                ))),
+                NanPolicy::String => {
+                    *self = Value::String(self.to_string());
+                    Ok(false)
+                }
+            },
+            Value::Array(array) => {
+                let mut dropped = Vec::new();
+                for (i, value) in array.iter_mut().enumerate() {
+                    if value.sanitize_child(path, &i.to_string(), policy)? {
+                        dropped.push(i);
+                    }
+                }
+                for i in dropped.into_iter().rev() {
+                    array.remove(i);
+                }
+                Ok(false)
+            }
+            Value::Table(table) => {
+                let mut dropped = Vec::new();
+                for (key, value) in table.iter_mut() {
+                    if value.sanitize_child(path, key, policy)? {
+                        dropped.push(key.clone());
+                    }
+                }
+                for key in dropped {
+                    table.shift_remove(&key);
+                }
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn sanitize_child(&mut self, path: &mut String, key: &str, policy: NanPolicy) -> Result<bool> {
+        let len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(key);
+        let dropped = self.sanitize_at(path, policy);
+        path.truncate(len);
+        dropped
+    }
+
+    /// Deep-merges `other` into this value. Tables are merged key by key;
+    /// anything else, arrays included, replaces the existing value.
+    pub fn merge(&mut self, other: Value) {
+        match (self, other) {
+            (Value::Table(table), Value::Table(other)) => {
+                for (key, value) in other {
+                    match table.get_mut(&key) {
+                        Some(existing) => existing.merge(value),
+                        None => {
+                            table.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Parses `text` as a document and deep-merges it into this value, for
+    /// layering configuration such as user overrides on top of defaults.
+    pub fn merge_str(&mut self, text: &str) -> Result<()> {
+        self.merge(crate::from_str(text)?);
+        Ok(())
+    }
+
+    /// Keeps only the top-level entries of a table for which `f`, given each
+    /// key and value, returns true. Nested tables are not visited. Values that
+    /// are not tables are left as they are.
+    pub fn retain(&mut self, mut f: impl FnMut(&str, &Value) -> bool) {
+        if let Value::Table(table) = self {
+            table.retain(|key, value| f(key, value));
+        }
+    }
+
+    /// Keeps only the values at the given dotted paths, such as
+    /// `server.tls.port`, along with the tables that lead to them, and removes
+    /// everything else. A path that ends at a table keeps all of it. Paths
+    /// are followed through tables only, so arrays are kept or removed whole.
+    pub fn retain_paths(&mut self, paths: &[&str]) {
+        let paths: Vec<Vec<&str>> = paths.iter().map(|path| path.split('.').collect()).collect();
+        let paths: Vec<&[&str]> = paths.iter().map(Vec::as_slice).collect();
+        self.retain_subpaths(&paths);
+    }
+
+    fn retain_subpaths(&mut self, paths: &[&[&str]]) {
+        let Value::Table(table) = self else {
+            return;
+        };
+        table.retain(|key, value| {
+            let subpaths: Vec<&[&str]> = paths
+                .iter()
+                .filter(|path| path.first() == Some(&key.as_str()))
+                .map(|path| &path[1..])
+                .collect();
+            if subpaths.is_empty() {
+                return false;
+            }
+            if subpaths.iter().any(|path| path.is_empty()) {
+                return true;
+            }
+            match value {
+                Value::Table(_) => {
+                    value.retain_subpaths(&subpaths);
+                    true
+                }
+                Value::Array(_) => true,
+                _ => false,
+            }
+        });
+    }
+}
+
+/// A line ending for [`Value::normalize_line_endings`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LineEnding {
+    /// `\n`, as on Unix.
+    Lf,
+    /// `\r\n`, as on Windows.
+    CrLf,
+}
+
+/// What [`Value::sanitize_for_json`] does with `nan` and `inf` floats.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NanPolicy {
+    /// Removes the value. TOML has no null, so the entry is dropped from its
+    /// table or array and a JSON writer sees it as missing.
+    Null,
+    /// Fails with an error naming the path of the first such value.
+    Error,
+    /// Replaces the value with the string `"nan"`, `"inf"` or `"-inf"`.
+    String,
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($($from:ty),*)),* $(,)?) => {
+        $($(
+            impl From<$from> for Value {
+                fn from(value: $from) -> Self {
+                    Value::$variant(value.into())
+                }
+            }
+        )*)*
+    };
+}
+
+impl_from! {
+    String(String, &str),
+    Integer(i8, i16, i32, i64, u8, u16, u32),
+    Float(f32, f64),
+    Bool(bool),
+    Array(Array),
+    Table(Table),
+}
+
+/// Formats the value as its variants, as a derived `Debug` would. With the
+/// alternate flag, as in `{:#?}`, writes an indented tree instead, in the
+/// style of JSON, with keys in sorted order and scalars in TOML syntax.
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.fmt_tree(f, 0);
+        }
+        match self {
+            Value::String(x) => f.debug_tuple("String").field(x).finish(),
+            Value::Integer(x) => f.debug_tuple("Integer").field(x).finish(),
+            Value::Float(x) => f.debug_tuple("Float").field(x).finish(),
+            Value::Bool(x) => f.debug_tuple("Bool").field(x).finish(),
+            Value::OffsetDateTime(x) => f.debug_tuple("OffsetDateTime").field(x).finish(),
+            Value::LocalDateTime(x) => f.debug_tuple("LocalDateTime").field(x).finish(),
+            Value::LocalDate(x) => f.debug_tuple("LocalDate").field(x).finish(),
+            Value::LocalTime(x) => f.debug_tuple("LocalTime").field(x).finish(),
+            Value::Array(x) => f.debug_tuple("Array").field(x).finish(),
+            Value::Table(x) => f.debug_tuple("Table").field(x).finish(),
+        }
+    }
+}
+
+impl Value {
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "    ".repeat(depth + 1);
+        let close = &indent[4..];
+        match self {
+            Value::Array(array) if !array.is_empty() => {
+                f.write_str("[\n")?;
+                for (i, value) in array.iter().enumerate() {
+                    f.write_str(&indent)?;
+                    value.fmt_tree(f, depth + 1)?;
+                    f.write_str(if i + 1 < array.len() { ",\n" } else { "\n" })?;
+                }
+                write!(f, "{close}]")
+            }
+            Value::Table(table) if !table.is_empty() => {
+                f.write_str("{\n")?;
+                let entries = self.sorted_entries();
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write!(f, "{indent}{}: ", ser::quote_basic_string(key))?;
+                    value.fmt_tree(f, depth + 1)?;
+                    f.write_str(if i + 1 < entries.len() { ",\n" } else { "\n" })?;
+                }
+                write!(f, "{close}}}")
+            }
+            value => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Formats the value using TOML value syntax. Tables are written as inline
+/// tables with their keys in sorted order.
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(string) => f.write_str(&ser::quote_basic_string(string)),
+            Value::Integer(int) => write!(f, "{int}"),
+            Value::Float(float) if float.is_nan() => write!(f, "nan"),
+            Value::Float(float) if float.is_infinite() => {
+                write!(f, "{}inf", if *float < 0.0 { "-" } else { "" })
+            }
+            Value::Float(float) => write!(f, "{float:?}"),
+            Value::Bool(bool) => write!(f, "{bool}"),
+            #[cfg(feature = "chrono")]
+            Value::OffsetDateTime(x) => {
+                write!(
+                    f,
+                    "{}",
+                    x.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                )
+            }
+            #[cfg(feature = "chrono")]
+            Value::LocalDateTime(x) => write!(f, "{}", x.format("%Y-%m-%dT%H:%M:%S%.f")),
+            #[cfg(not(feature = "chrono"))]
+            Value::OffsetDateTime(x) | Value::LocalDateTime(x) => write!(f, "{x}"),
+            Value::LocalDate(x) => write!(f, "{x}"),
+            Value::LocalTime(x) => write!(f, "{x}"),
+            Value::Array(array) => {
+                f.write_char('[')?;
+                for (i, value) in array.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Value::Table(table) if table.is_empty() => f.write_str("{}"),
+            Value::Table(_) => {
+                f.write_str("{ ")?;
+                for (i, (key, value)) in self.sorted_entries().into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{} = {value}", ser::quote_key_if_needed(key))?;
+                }
+                f.write_str(" }")
+            }
+        }
+    }
 }
 
 impl Index<&str> for Value {
@@ -156,3 +1144,65 @@ impl IndexMut<usize> for Value {
         }
     }
 }
+
+/// A possibly missing value, returned by [`Value::at`]. Looking up a key or
+/// index on a missing value, or on a value of the wrong type, gives another
+/// missing value.
+#[derive(Debug, Clone, Copy)]
+pub struct ValueRef<'a>(Option<&'a Value>);
+
+impl<'a> ValueRef<'a> {
+    pub fn at(self, key: &str) -> ValueRef<'a> {
+        match self.0 {
+            Some(Value::Table(table)) => ValueRef(table.get(key)),
+            _ => ValueRef(None),
+        }
+    }
+
+    pub fn at_index(self, index: usize) -> ValueRef<'a> {
+        match self.0 {
+            Some(Value::Array(array)) => ValueRef(array.get(index)),
+            _ => ValueRef(None),
+        }
+    }
+
+    pub fn get(self) -> Option<&'a Value> {
+        self.0
+    }
+}
+
+/// Iterates over the elements of an array. Use [`Value::into_entries`] to
+/// iterate over the entries of a table.
+///
+/// # Panics
+///
+/// Panics if the value is not an array.
+impl IntoIterator for Value {
+    type Item = Value;
+    type IntoIter = vec::IntoIter<Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Value::Array(array) => array.into_iter(),
+            _ => panic!("not an array value"),
+        }
+    }
+}
+
+/// Iterates over references to the elements of an array. Use
+/// [`Value::entries`] to iterate over the entries of a table.
+///
+/// # Panics
+///
+/// Panics if the value is not an array.
+impl<'a> IntoIterator for &'a Value {
+    type Item = &'a Value;
+    type IntoIter = slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Value::Array(array) => array.iter(),
+            _ => panic!("not an array value"),
+        }
+    }
+}
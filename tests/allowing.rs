@@ -0,0 +1,28 @@
+use toml::{from_str_allowing, Error, Result};
+
+#[test]
+fn all_allowed() -> Result<()> {
+    let text = r#"
+title = "example"
+
+[database]
+port = 5432
+"#;
+    let root = from_str_allowing(text, &["title", "database", "logging"])?;
+    assert_eq!(root["database"]["port"].as_int(), 5432);
+    Ok(())
+}
+
+#[test]
+fn unexpected_key() -> Result<()> {
+    let text = r#"
+title = "example"
+
+[databse]
+port = 5432
+"#;
+    let err = from_str_allowing(text, &["title", "database"]).unwrap_err();
+    assert_eq!(err, Error::UnexpectedKey("databse".into()));
+    assert_eq!(err.to_string(), "unexpected key `databse`");
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use toml::{from_str, Result};
+use toml::{from_str, Error, Position, Result};
 
 #[test]
 fn array() -> Result<()> {
@@ -90,3 +90,131 @@ integers3 = [
 
     Ok(())
 }
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn bools() -> Result<()> {
+    let text = "x = [true, false,true]";
+    let root = from_str(text)?;
+    assert_eq!(root["x"][0].as_bool(), true);
+    assert_eq!(root["x"][1].as_bool(), false);
+    assert_eq!(root["x"][2].as_bool(), true);
+    Ok(())
+}
+
+#[test]
+fn bare_words() -> Result<()> {
+    let text = "x = [on, off]";
+    let root = from_str(text);
+    assert_eq!(root, Err(Error::InvalidValue("on".into())));
+    let text = r#"x = ["on", off]"#;
+    let root = from_str(text);
+    assert_eq!(root, Err(Error::InvalidValue("off".into())));
+    Ok(())
+}
+
+#[test]
+fn large_arrays() -> Result<()> {
+    let numbers: Vec<_> = (0..1000).map(|i| i.to_string()).collect();
+    let numbers = numbers.join(", ");
+    let text = format!("numbers = [{numbers}]\nnested = [[{numbers}], []]\n");
+    let root = from_str(&text)?;
+    assert_eq!(root["numbers"].as_arr().len(), 1000);
+    assert_eq!(root["numbers"][999].as_int(), 999);
+    assert_eq!(root["nested"][0].as_arr().len(), 1000);
+    // the array is still recorded as inline once moved into the document
+    assert!(from_str(&format!("{text}[[numbers]]\n")).is_err());
+    Ok(())
+}
+
+#[test]
+fn empty_elements() -> Result<()> {
+    let text = r#"
+empty_arrays = [[], []]
+empty_tables = [{}, { }]
+mixed = [ [ ], {}, [[]] ]
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["empty_arrays"].as_arr().len(), 2);
+    assert!(root["empty_arrays"][0].as_arr().is_empty());
+    assert!(root["empty_arrays"][1].as_arr().is_empty());
+    assert_eq!(root["empty_tables"].as_arr().len(), 2);
+    assert!(root["empty_tables"][0].as_table().is_empty());
+    assert!(root["empty_tables"][1].as_table().is_empty());
+    assert!(root["mixed"][0].as_arr().is_empty());
+    assert!(root["mixed"][1].as_table().is_empty());
+    assert!(root["mixed"][2][0].as_arr().is_empty());
+    Ok(())
+}
+
+#[test]
+fn comments_and_blank_lines() -> Result<()> {
+    let text = "
+tabs = [
+\t1,
+\t2
+]
+comments = [ # opening comment
+
+  # a line with only a comment
+  1, # trailing comment
+
+\t
+  2, # another
+  # before the end
+]
+";
+    let root = from_str(text)?;
+    assert_eq!(root["tabs"].as_arr().len(), 2);
+    assert_eq!(root["comments"][0].as_int(), 1);
+    assert_eq!(root["comments"][1].as_int(), 2);
+    assert_eq!(root["comments"].as_arr().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn empty_multiline() -> Result<()> {
+    let text = "
+newline = [
+]
+blank = [
+\t
+]
+comment = [ # nothing here
+]
+";
+    let root = from_str(text)?;
+    assert!(root["newline"].as_arr().is_empty());
+    assert!(root["blank"].as_arr().is_empty());
+    assert!(root["comment"].as_arr().is_empty());
+    Ok(())
+}
+
+#[test]
+fn unterminated() -> Result<()> {
+    let position = Position { line: 1, column: 5 };
+    assert_eq!(
+        from_str("x = [1, 2"),
+        Err(Error::UnterminatedArray(position))
+    );
+    assert_eq!(
+        from_str("x = [1, 2,"),
+        Err(Error::UnterminatedArray(position))
+    );
+    assert_eq!(
+        from_str("x = [\n  1,\n"),
+        Err(Error::UnterminatedArray(position))
+    );
+    assert_eq!(
+        from_str("x = [[1], [2"),
+        Err(Error::UnterminatedArray(Position {
+            line: 1,
+            column: 11
+        }))
+    );
+    assert_eq!(
+        from_str("x = [1, 2").unwrap_err().to_string(),
+        "unterminated array starting at line 1, column 5"
+    );
+    Ok(())
+}
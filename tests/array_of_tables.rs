@@ -1,4 +1,4 @@
-use toml::{from_str, Result};
+use toml::{from_str, Result, Value, ValueKind};
 
 #[test]
 fn array_of_tables() -> Result<()> {
@@ -187,3 +187,251 @@ fn inline_tables_2() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn quoted_key_matching_inline_array() -> Result<()> {
+    let text = r#"
+"fruits.varieties" = []
+
+[[fruits.varieties]]
+name = "red delicious"
+"#;
+    let root = from_str(text)?;
+
+    assert!(root["fruits.varieties"].as_arr().is_empty());
+    assert_eq!(
+        root["fruits"]["varieties"][0]["name"].as_str(),
+        "red delicious"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn array_of_tables_at_eof() -> Result<()> {
+    let text = "[[products]]";
+    let root = from_str(text)?;
+
+    assert!(root["products"][0].as_table().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn dotted_array_of_tables_at_eof() -> Result<()> {
+    let text = "[[fruits]]\nname = \"apple\"\n[[fruits.varieties]]";
+    let root = from_str(text)?;
+
+    assert_eq!(root["fruits"][0]["name"].as_str(), "apple");
+    assert!(root["fruits"][0]["varieties"][0].as_table().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn malformed_header_keys() -> Result<()> {
+    for text in ["[[a.]]", "[[.a]]", "[[a..b]]"] {
+        let root = from_str(text);
+        assert!(root.is_err(), "{text} should not parse");
+    }
+    Ok(())
+}
+
+#[test]
+fn key_value_on_header_line() -> Result<()> {
+    let text = r#"
+[[products]] name = "Hammer"
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn redefine_static_array() -> Result<()> {
+    let text = r#"
+# INVALID TOML DOC
+fruits = [1, 2]
+
+[[fruits]] # Not allowed
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn extend_static_array() -> Result<()> {
+    let texts = [
+        "fruits = [1, 2]\n[[fruits.apple]]",
+        "fruits = []\n[fruits.apple]",
+        "fruits = [{ name = \"apple\" }]\n[fruits.apple]",
+        "fruits = [{ name = \"apple\" }]\n[[fruits.apple]]",
+        "fruits = [{ name = \"apple\" }]\nfruits.apple = 1",
+    ];
+    for text in texts {
+        let root = from_str(text);
+        assert!(root.is_err(), "{text:?} should not parse");
+    }
+    Ok(())
+}
+
+#[test]
+fn mixed_quoting_in_header() -> Result<()> {
+    let text = r#"
+[[a."b".c]]
+key = 1
+
+[[a."b".c]]
+key = 2
+
+[[a.'b'."c"]]
+key = 3
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["a"]["b"]["c"].as_arr().len(), 3);
+    assert_eq!(root["a"]["b"]["c"][0]["key"].as_int(), 1);
+    assert_eq!(root["a"]["b"]["c"][2]["key"].as_int(), 3);
+    Ok(())
+}
+
+#[test]
+fn dotted_key_inside_element() -> Result<()> {
+    let text = r#"
+[[servers]]
+name = "alpha"
+
+[[servers]]
+servers.name = "beta"
+"#;
+    let root = from_str(text)?;
+    // keys are relative to the element, so this defines a nested table
+    assert_eq!(root["servers"][0]["name"].as_str(), "alpha");
+    assert_eq!(root["servers"][1]["servers"]["name"].as_str(), "beta");
+    Ok(())
+}
+
+#[test]
+fn dotted_key_inside_element_redefined() -> Result<()> {
+    let text = r#"
+# INVALID TOML DOC
+[[servers]]
+servers.name = "beta"
+servers = "gamma" # Not allowed
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn redefine_as_table() -> Result<()> {
+    let text = r#"
+# INVALID TOML DOC
+[[servers]]
+name = "alpha"
+
+[servers] # Not allowed
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn redefine_as_value() -> Result<()> {
+    let text = r#"
+# INVALID TOML DOC
+servers = "alpha"
+
+[[servers]] # Not allowed
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn implicit_parent_table() -> Result<()> {
+    let text = "[[a.b]]\nx = 1\n[[a.b]]";
+    let root = from_str(text)?;
+
+    assert_eq!(root["a"].kind(), ValueKind::Table);
+    assert_eq!(root["a"].as_table().len(), 1);
+    assert_eq!(root["a"]["b"].kind(), ValueKind::Array);
+    assert_eq!(root["a"]["b"][0]["x"].as_int(), 1);
+    assert!(root["a"]["b"][1].as_table().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn implicit_parent_tables_nested() -> Result<()> {
+    let text = "[[a.b.c]]";
+    let root = from_str(text)?;
+
+    assert_eq!(root["a"].kind(), ValueKind::Table);
+    assert_eq!(root["a"]["b"].kind(), ValueKind::Table);
+    assert_eq!(root["a"]["b"]["c"].kind(), ValueKind::Array);
+    assert_eq!(root["a"]["b"]["c"].as_arr().len(), 1);
+    assert!(root["a"]["b"]["c"][0].as_table().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn tables() -> Result<()> {
+    let text = r#"
+[[products]]
+name = "Hammer"
+sku = 738594937
+
+[[products]]  # empty table within the array
+
+[[products]]
+name = "Nail"
+sku = 284758393
+
+color = "gray"
+"#;
+    let root = from_str(text)?;
+
+    let unnamed = Value::String("?".into());
+    let names: Vec<_> = root["products"]
+        .tables()
+        .map(|product| product.get_or("name", &unnamed).as_str())
+        .collect();
+    assert_eq!(names, ["Hammer", "?", "Nail"]);
+
+    let root = from_str("mixed = [1, { a = 1 }, \"x\", { a = 2 }]\nscalar = 1")?;
+    let values: Vec<_> = root["mixed"].tables().map(|t| t["a"].as_int()).collect();
+    assert_eq!(values, [1, 2]);
+    assert_eq!(root["scalar"].tables().count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn dotted_keys_per_element() -> Result<()> {
+    let text = "[[a]]\nb = 1\n[[a]]\nb = 2";
+    let root = from_str(text)?;
+    assert_eq!(root["a"][0]["b"].as_int(), 1);
+    assert_eq!(root["a"][1]["b"].as_int(), 2);
+
+    let text = "[[a]]\nb.c = 1\n[[a]]\nb.c = 2\nb.d = 3\n[a.b.e]";
+    let root = from_str(text)?;
+    assert_eq!(root["a"][0]["b"].as_table().len(), 1);
+    assert_eq!(root["a"][0]["b"]["c"].as_int(), 1);
+    assert_eq!(root["a"][1]["b"].as_table().len(), 3);
+    assert_eq!(root["a"][1]["b"]["c"].as_int(), 2);
+    assert_eq!(root["a"][1]["b"]["d"].as_int(), 3);
+
+    // the dotted key defines `b` in the first element only
+    let text = "[[a]]\nb.c = 1\n[[a]]\n[a.b]\nc = 2";
+    let root = from_str(text)?;
+    assert_eq!(root["a"][1]["b"]["c"].as_int(), 2);
+    let text = "[[a]]\nb.c = 1\n[a.b]\nd = 2";
+    assert!(from_str(text).is_err());
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use toml::{from_str, Result};
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn bool() -> Result<()> {
     let text = "
 bool1 = true
@@ -11,3 +12,13 @@ bool2 = false
     assert_eq!(root["bool2"].as_bool(), false);
     Ok(())
 }
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn inline_table() -> Result<()> {
+    let text = "flags = { debug = true, verbose = false }";
+    let root = from_str(text)?;
+    assert_eq!(root["flags"]["debug"].as_bool(), true);
+    assert_eq!(root["flags"]["verbose"].as_bool(), false);
+    Ok(())
+}
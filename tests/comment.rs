@@ -9,3 +9,57 @@ another = "# This is not a comment""###;
     assert_eq!(root["key"].as_str(), "value");
     Ok(())
 }
+
+#[test]
+fn empty_comment() -> toml::Result<()> {
+    let text = "#\nkey = \"value\"\n";
+    let root = from_str(text)?;
+    assert_eq!(root["key"].as_str(), "value");
+    Ok(())
+}
+
+#[test]
+fn double_hash_comment() -> toml::Result<()> {
+    let text = "##\nkey = \"value\" ##\n";
+    let root = from_str(text)?;
+    assert_eq!(root["key"].as_str(), "value");
+    Ok(())
+}
+
+#[test]
+fn empty_comment_at_eof() -> toml::Result<()> {
+    let text = "key = \"value\"\n#";
+    let root = from_str(text)?;
+    assert_eq!(root["key"].as_str(), "value");
+    assert_eq!(from_str("#")?.as_table().len(), 0);
+    Ok(())
+}
+
+#[test]
+fn comment_crlf_at_eof() -> toml::Result<()> {
+    let text = "key = \"value\" # comment\r\n";
+    let root = from_str(text)?;
+    assert_eq!(root["key"].as_str(), "value");
+    Ok(())
+}
+
+#[test]
+fn comment_flush_against_header() -> toml::Result<()> {
+    let root = from_str("[table]#c\nkey = 1")?;
+    assert_eq!(root["table"]["key"].as_int(), 1);
+    let root = from_str("[[a]]#c\nkey = 1")?;
+    assert_eq!(root["a"][0]["key"].as_int(), 1);
+    Ok(())
+}
+
+#[test]
+fn comment_flush_against_value() -> toml::Result<()> {
+    let text = "key = 1#c\nflt = 1.5#c\nstr = \"s\"#c\narr = [1]#c\ntbl = {a=1}#c";
+    let root = from_str(text)?;
+    assert_eq!(root["key"].as_int(), 1);
+    assert_eq!(root["flt"].as_float(), 1.5);
+    assert_eq!(root["str"].as_str(), "s");
+    assert_eq!(root["arr"][0].as_int(), 1);
+    assert_eq!(root["tbl"]["a"].as_int(), 1);
+    Ok(())
+}
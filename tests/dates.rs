@@ -1,7 +1,10 @@
+#![cfg(feature = "chrono")]
+
 use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Timelike};
-use toml::{from_str, Result};
+use toml::{from_str, Error, Position, Result, Value, ValueKind};
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn offset_date_time() -> Result<()> {
     let text = "
 odt1 = 1979-05-27T07:32:00Z
@@ -49,6 +52,7 @@ odt4 = 1979-05-27 07:32:00Z
 }
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn local_date_time() -> Result<()> {
     let text = "
 ldt1 = 1979-05-27T07:32:00
@@ -76,6 +80,7 @@ ldt2 = 1979-05-27T00:32:00.999999
 }
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn local_date() -> Result<()> {
     let text = "ld1 = 1979-05-27";
     let root = from_str(text)?;
@@ -89,6 +94,7 @@ fn local_date() -> Result<()> {
 }
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn local_time() -> Result<()> {
     let text = "
 lt1 = 07:32:00
@@ -108,3 +114,94 @@ lt2 = 00:32:00.999999
 
     Ok(())
 }
+
+#[test]
+fn time_with_separator_but_no_date() {
+    let text = "x = T07:32:00";
+    assert_eq!(from_str(text), Err(Error::InvalidValue("T07:32:00".into())));
+}
+
+#[test]
+fn timestamp() -> Result<()> {
+    let text = "
+odt = 1979-05-27T07:32:00-08:00
+ldt = 1979-05-27T07:32:00
+ld = 1979-05-27
+";
+    let root = from_str(text)?;
+    let pst = FixedOffset::west_opt(8 * 3600).unwrap();
+    let utc = FixedOffset::east_opt(0).unwrap();
+    assert_eq!(
+        root["odt"].as_timestamp(),
+        Some(pst.with_ymd_and_hms(1979, 5, 27, 7, 32, 0).unwrap())
+    );
+    assert_eq!(
+        root["ldt"].as_timestamp(),
+        Some(utc.with_ymd_and_hms(1979, 5, 27, 7, 32, 0).unwrap())
+    );
+    assert_eq!(
+        root["ldt"].as_timestamp_with_offset(pst),
+        Some(pst.with_ymd_and_hms(1979, 5, 27, 7, 32, 0).unwrap())
+    );
+    assert_eq!(root["ld"].as_timestamp(), None);
+    Ok(())
+}
+
+#[test]
+fn lowercase_separator_and_offset() -> Result<()> {
+    let text = "
+odt = 1979-05-27t07:32:00z
+ldt = 1979-05-27t07:32:00
+";
+    let root = from_str(text)?;
+    let expected = from_str("odt = 1979-05-27T07:32:00Z\nldt = 1979-05-27T07:32:00")?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn mixed_array() -> Result<()> {
+    let text = "dates = [1979-05-27T07:32:00Z, 1979-05-27T07:32:00, 1979-05-27, 07:32:00]";
+    let root = from_str(text)?;
+    let kinds: Vec<_> = root["dates"].as_arr().iter().map(Value::kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValueKind::OffsetDateTime,
+            ValueKind::LocalDateTime,
+            ValueKind::LocalDate,
+            ValueKind::LocalTime,
+        ]
+    );
+    assert_eq!(
+        root["dates"][2].as_local_date(),
+        NaiveDate::from_ymd_opt(1979, 5, 27).unwrap()
+    );
+    assert_eq!(
+        root["dates"][3].as_local_time(),
+        NaiveTime::from_hms_opt(7, 32, 0).unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn malformed_offset() {
+    for (text, offset, column) in [
+        ("odt = 1979-05-27T07:32:00+07", "+07", 26),
+        ("odt = 1979-05-27T07:32:00+07:00:30", "+07:00:30", 26),
+        ("odt = [1979-05-27 07:32:00.5-0700]", "-0700", 29),
+    ] {
+        let err = from_str(text).unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidOffset {
+                offset: offset.into(),
+                position: Position { line: 1, column },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("invalid offset `{offset}` in date-time (line 1, column {column})")
+        );
+    }
+}
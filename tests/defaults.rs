@@ -0,0 +1,49 @@
+use toml::{from_str, from_str_with_defaults, Result};
+
+#[test]
+fn overrides_and_additions() -> Result<()> {
+    let defaults = r#"
+title = "example"
+
+[server]
+host = "localhost"
+port = 8080
+
+[logging]
+level = "info"
+"#;
+    let overrides = r#"
+[server]
+port = 9090
+tls = true
+
+[cache]
+enabled = true
+"#;
+    let root = from_str_with_defaults(defaults, overrides)?;
+    let expected = from_str(
+        r#"
+title = "example"
+
+[server]
+host = "localhost"
+port = 9090
+tls = true
+
+[logging]
+level = "info"
+
+[cache]
+enabled = true
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn invalid_document() -> Result<()> {
+    assert!(from_str_with_defaults("a = 1", "b =").is_err());
+    assert!(from_str_with_defaults("a =", "b = 1").is_err());
+    Ok(())
+}
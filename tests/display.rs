@@ -0,0 +1,121 @@
+use toml::{from_str, Result, Table, Value};
+
+#[test]
+fn string() {
+    let value = Value::String("I'm a \"string\".\n\tC:\\path".into());
+    assert_eq!(value.to_string(), r#""I'm a \"string\".\n\tC:\\path""#);
+}
+
+#[test]
+fn integer() {
+    assert_eq!(Value::Integer(-17).to_string(), "-17");
+}
+
+#[test]
+fn float() {
+    assert_eq!(Value::Float(1.0).to_string(), "1.0");
+    assert_eq!(Value::Float(6.626e-34).to_string(), "6.626e-34");
+    assert_eq!(Value::Float(f64::INFINITY).to_string(), "inf");
+    assert_eq!(Value::Float(f64::NEG_INFINITY).to_string(), "-inf");
+    assert_eq!(Value::Float(f64::NAN).to_string(), "nan");
+}
+
+#[test]
+fn bool() {
+    assert_eq!(Value::Bool(true).to_string(), "true");
+    assert_eq!(Value::Bool(false).to_string(), "false");
+}
+
+#[test]
+fn dates() -> Result<()> {
+    let text = "
+odt = 1979-05-27T00:32:00.999999-07:00
+utc = 1979-05-27 07:32:00Z
+ldt = 1979-05-27T07:32:00
+ld = 1979-05-27
+lt = 07:32:00.999
+";
+    let root = from_str(text)?;
+    assert_eq!(root["odt"].to_string(), "1979-05-27T00:32:00.999999-07:00");
+    assert_eq!(root["utc"].to_string(), "1979-05-27T07:32:00Z");
+    assert_eq!(root["ldt"].to_string(), "1979-05-27T07:32:00");
+    assert_eq!(root["ld"].to_string(), "1979-05-27");
+    assert_eq!(root["lt"].to_string(), "07:32:00.999");
+    Ok(())
+}
+
+#[test]
+fn array() {
+    let value = Value::Array(vec![
+        Value::Integer(1),
+        Value::String("two".into()),
+        Value::Array(vec![]),
+    ]);
+    assert_eq!(value.to_string(), r#"[1, "two", []]"#);
+}
+
+#[test]
+fn table() -> Result<()> {
+    let text = r#"
+point = { y = 2, x = 1, "z axis" = { depth = 3 } }
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root["point"].to_string(),
+        r#"{ x = 1, y = 2, "z axis" = { depth = 3 } }"#
+    );
+    assert_eq!(Value::Table(Table::new()).to_string(), "{}");
+    Ok(())
+}
+
+#[test]
+fn round_trip() -> Result<()> {
+    let text = r#"
+value = { name = "Nail", sizes = [1, 2.5], flags = { sharp = true } }
+"#;
+    let root = from_str(text)?;
+    let reparsed = from_str(&format!("value = {}", root["value"]))?;
+    assert_eq!(reparsed, root);
+    Ok(())
+}
+
+#[test]
+fn debug_alternate() -> Result<()> {
+    let text = r#"
+title = "TOML Example"
+empty = []
+
+[database]
+ports = [8000, 8001]
+options = {}
+
+[[servers]]
+name = "alpha"
+"#;
+    let root = from_str(text)?;
+    let expected = r#"{
+    "database": {
+        "options": {},
+        "ports": [
+            8000,
+            8001
+        ]
+    },
+    "empty": [],
+    "servers": [
+        {
+            "name": "alpha"
+        }
+    ],
+    "title": "TOML Example"
+}"#;
+    assert_eq!(format!("{root:#?}"), expected);
+    assert_eq!(format!("{:#?}", Value::Integer(1)), "1");
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let value = Value::Array(vec![Value::Integer(1), Value::String("a".into())]);
+    assert_eq!(format!("{value:?}"), r#"Array([Integer(1), String("a")])"#);
+}
@@ -0,0 +1,34 @@
+use toml::{Document, Result};
+
+#[test]
+fn source_of() -> Result<()> {
+    let text = r#"
+title = "TOML Example"  # the title
+
+[owner]
+dob = 1979-05-27T07:32:00-08:00
+pets = [ "cat",
+         "dog" ]
+
+[[servers]]
+host = 'alpha'
+
+[[servers]]
+host = 'beta'
+"#;
+    let document = Document::parse(text)?;
+    assert_eq!(document.root()["owner"]["pets"][1].as_str(), "dog");
+    assert_eq!(document.source_of("title"), Some(r#""TOML Example""#));
+    assert_eq!(
+        document.source_of("owner.dob"),
+        Some("1979-05-27T07:32:00-08:00")
+    );
+    assert_eq!(
+        document.source_of("owner.pets"),
+        Some("[ \"cat\",\n         \"dog\" ]")
+    );
+    assert_eq!(document.source_of("servers.1.host"), Some("'beta'"));
+    assert_eq!(document.source_of("owner"), None);
+    assert_eq!(document.source_of("missing"), None);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use toml::{expand_env, from_str, Result};
+
+fn lookup(var: &str) -> Option<String> {
+    match var {
+        "DB_HOST" => Some("localhost".into()),
+        "DB_PORT" => Some("5432".into()),
+        _ => None,
+    }
+}
+
+#[test]
+fn present() -> Result<()> {
+    let text = r#"
+url = "postgres://${DB_HOST}:${DB_PORT}/db"
+[replicas]
+hosts = ["${DB_HOST}", "backup"]
+"#;
+    let mut root = from_str(text)?;
+    expand_env(&mut root, lookup);
+    assert_eq!(root["url"].as_str(), "postgres://localhost:5432/db");
+    assert_eq!(root["replicas"]["hosts"][0].as_str(), "localhost");
+    assert_eq!(root["replicas"]["hosts"][1].as_str(), "backup");
+    Ok(())
+}
+
+#[test]
+fn absent() -> Result<()> {
+    let text = r#"
+url = "postgres://${DB_USER}@${DB_HOST}/db"
+unterminated = "${DB_HOST"
+"#;
+    let mut root = from_str(text)?;
+    expand_env(&mut root, lookup);
+    assert_eq!(root["url"].as_str(), "postgres://${DB_USER}@localhost/db");
+    assert_eq!(root["unterminated"].as_str(), "${DB_HOST");
+    Ok(())
+}
+
+#[test]
+fn escaped() -> Result<()> {
+    let text = r#"
+price = "$$5 at $${DB_HOST}, $ alone"
+"#;
+    let mut root = from_str(text)?;
+    expand_env(&mut root, lookup);
+    assert_eq!(root["price"].as_str(), "$5 at ${DB_HOST}, $ alone");
+    Ok(())
+}
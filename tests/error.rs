@@ -0,0 +1,100 @@
+use toml::{from_str, Error, Position};
+
+#[test]
+fn snippet() {
+    let text = r#"
+[[products]] name = "Hammer"
+"#;
+    let err = from_str(text).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Syntax {
+            position: Position {
+                line: 2,
+                column: 14
+            },
+            line: r#"[[products]] name = "Hammer""#.into(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        r#"parse error at line 2, column 14
+2 | [[products]] name = "Hammer"
+  |              ^"#
+    );
+}
+
+#[test]
+fn snippet_keeps_tabs() {
+    let text = "a = 1\n\t[b.]\n";
+    let err = from_str(text).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "parse error at line 2, column 5\n2 | \t[b.]\n  | \t   ^"
+    );
+}
+
+#[test]
+fn unexpected_token_at_top_level() {
+    for token in ["]", "}", "=", ","] {
+        let text = format!("a = 1\n{token}\n");
+        let err = from_str(&text).unwrap_err();
+        assert_eq!(
+            err,
+            Error::UnexpectedToken {
+                token: token.into(),
+                context: "at top level",
+                position: Position { line: 2, column: 1 },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("unexpected `{token}` at top level (line 2, column 1)")
+        );
+    }
+}
+
+#[test]
+fn unexpected_token_in_value_position() {
+    let err = from_str("a == 1").unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnexpectedToken {
+            token: "=".into(),
+            context: "in value position",
+            position: Position { line: 1, column: 4 },
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "unexpected `=` in value position (line 1, column 4)"
+    );
+    let err = from_str("a = = 1").unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnexpectedToken {
+            token: "=".into(),
+            context: "in value position",
+            position: Position { line: 1, column: 5 },
+        }
+    );
+}
+
+#[test]
+fn lone_dot_in_value_position() {
+    for (text, column) in [("x = .", 5), ("x = ..", 5), ("x = [1, .]", 9)] {
+        let err = from_str(text).unwrap_err();
+        assert_eq!(
+            err,
+            Error::UnexpectedToken {
+                token: ".".into(),
+                context: "in value position",
+                position: Position { line: 1, column },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("unexpected `.` in value position (line 1, column {column})")
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use toml::{escape_basic_string, from_str, unescape_basic_string, Error, Result};
+
+#[test]
+fn escape() {
+    assert_eq!(
+        escape_basic_string("\u{8}\t\n\u{C}\r\"\\\u{1}\u{7F}é"),
+        r#"\b\t\n\f\r\"\\\u0001\u007Fé"#
+    );
+    assert_eq!(escape_basic_string("plain"), "plain");
+}
+
+#[test]
+fn unescape() -> Result<()> {
+    assert_eq!(
+        unescape_basic_string(r#"\b\t\n\f\r\"\\é\U0001F600"#)?,
+        "\u{8}\t\n\u{C}\r\"\\é😀"
+    );
+    assert_eq!(unescape_basic_string(r"\\n")?, "\\n");
+    Ok(())
+}
+
+#[test]
+fn unescape_invalid() {
+    assert_eq!(
+        unescape_basic_string(r"a\xb"),
+        Err(Error::InvalidValue(r"\x".into()))
+    );
+    assert_eq!(
+        unescape_basic_string(r"\uD800"),
+        Err(Error::InvalidValue(r"\uD800".into()))
+    );
+    assert_eq!(
+        unescape_basic_string(r"\u12"),
+        Err(Error::InvalidValue(r"\u12".into()))
+    );
+    assert_eq!(
+        unescape_basic_string("\\"),
+        Err(Error::InvalidValue("\\".into()))
+    );
+}
+
+#[test]
+fn round_trip() -> Result<()> {
+    let original = "\u{8}\t\n\u{C}\r\"\\\u{0}\u{1F}\u{7F} café 😀";
+    let escaped = escape_basic_string(original);
+    assert_eq!(unescape_basic_string(&escaped)?, original);
+
+    let root = from_str(&format!("s = \"{escaped}\""))?;
+    assert_eq!(root["s"].as_str(), original);
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use toml::{from_str, Result};
+use toml::{from_str, Error, Position, Result, ValueKind};
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn basic_float() -> Result<()> {
     let text = "
 # fractional
@@ -87,3 +88,81 @@ sf6 = -nan # valid, actual encoding is implementation-specific
     assert_eq!(root["sf6"].as_float(), f64::NAN);
     Ok(())
 }
+
+#[test]
+fn special_values_are_floats() -> Result<()> {
+    let text = "
+inf = inf
+nan = nan
+int = 3
+";
+    let root = from_str(text)?;
+    assert_eq!(root["inf"].kind(), ValueKind::Float);
+    assert_eq!(root["nan"].kind(), ValueKind::Float);
+    assert_eq!(root["inf"].as_number(), f64::INFINITY);
+    assert!(root["nan"].as_number().is_nan());
+    assert_eq!(root["int"].as_number(), 3.0);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "not a int value")]
+fn special_values_are_not_ints() {
+    let root = from_str("inf = inf").unwrap();
+    root["inf"].as_int();
+}
+
+#[test]
+fn missing_digit_messages() -> Result<()> {
+    let err = from_str("flt = .7").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "floats must have a digit before the decimal point (line 1, column 7)"
+    );
+    let err = from_str("flt = -.7").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "floats must have a digit before the decimal point (line 1, column 7)"
+    );
+    let err = from_str("flt = 7.").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "floats must have a digit after the decimal point (line 1, column 7)"
+    );
+    let err = from_str("flt = [1.0, 3.e+20]").unwrap_err();
+    assert_eq!(
+        err,
+        Error::MalformedFloat {
+            reason: "floats must have a digit after the decimal point",
+            position: Position {
+                line: 1,
+                column: 13
+            },
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn trailing_characters() -> Result<()> {
+    assert_eq!(
+        from_str("x = 1.2.3"),
+        Err(Error::TrailingCharacters {
+            value: "1.2.3".into(),
+            position: Position { line: 1, column: 5 },
+        })
+    );
+    assert_eq!(
+        from_str("x = [0, 1e2e3]"),
+        Err(Error::TrailingCharacters {
+            value: "1e2e3".into(),
+            position: Position { line: 1, column: 9 },
+        })
+    );
+    let err = from_str("x = 1e2e3").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected characters after number in `1e2e3` (line 1, column 5)"
+    );
+    Ok(())
+}
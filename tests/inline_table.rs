@@ -1,4 +1,4 @@
-use toml::{from_str, Result};
+use toml::{from_str, Error, Position, Result};
 
 #[test]
 fn inline_table() -> Result<()> {
@@ -39,3 +39,71 @@ type = { edible = false }  # INVALID
     assert!(root.is_err());
     Ok(())
 }
+
+#[test]
+fn sibling_with_shared_prefix() -> Result<()> {
+    let text = r#"
+type = { name = "Nail" }
+typeface = "serif"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["type"]["name"].as_str(), "Nail");
+    assert_eq!(root["typeface"].as_str(), "serif");
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn dotted_key_matching_quoted_inline_table() -> Result<()> {
+    let text = r#"
+[site]
+"google.com" = { visits = 1 }
+
+[site.google]
+com = true
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["site"]["google.com"]["visits"].as_int(), 1);
+    assert_eq!(root["site"]["google"]["com"].as_bool(), true);
+    Ok(())
+}
+
+#[test]
+fn empty_values() -> Result<()> {
+    let text = r#"
+empty = {}
+nested = { a = {}, b = [] }
+deep = { x = { y = { z = {} } } }
+"#;
+    let root = from_str(text)?;
+    assert!(root["empty"].as_table().is_empty());
+    assert_eq!(root["nested"].as_table().len(), 2);
+    assert!(root["nested"]["a"].as_table().is_empty());
+    assert!(root["nested"]["b"].as_arr().is_empty());
+    assert!(root["deep"]["x"]["y"]["z"].as_table().is_empty());
+    Ok(())
+}
+
+#[test]
+fn newline() -> Result<()> {
+    let err = from_str("point = { x = 1,\n y = 2 }").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "newlines are not allowed in inline tables (TOML 1.0) at line 1, column 17"
+    );
+    assert_eq!(
+        from_str("point = {\n}"),
+        Err(Error::NewlineInInlineTable(Position {
+            line: 1,
+            column: 10
+        }))
+    );
+    assert_eq!(
+        from_str("point = { x = 1 # comment\n}"),
+        Err(Error::NewlineInInlineTable(Position {
+            line: 1,
+            column: 26
+        }))
+    );
+    Ok(())
+}
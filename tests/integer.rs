@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use toml::{from_str, Result};
 
 #[test]
@@ -67,3 +69,56 @@ bin1 = 0b11010110
     assert_eq!(root["bin1"].as_int(), 214);
     Ok(())
 }
+
+#[test]
+fn minimal_radix() -> Result<()> {
+    let text = "
+hex1 = 0x0
+hex2 = 0x00
+hex3 = 0x0_0
+oct1 = 0o0
+oct2 = 0o0755
+bin1 = 0b0
+bin2 = 0b0_1
+";
+    let root = from_str(text)?;
+    assert_eq!(root["hex1"].as_int(), 0);
+    assert_eq!(root["hex2"].as_int(), 0);
+    assert_eq!(root["hex3"].as_int(), 0);
+    assert_eq!(root["oct1"].as_int(), 0);
+    assert_eq!(root["oct2"].as_int(), 493);
+    assert_eq!(root["bin1"].as_int(), 0);
+    assert_eq!(root["bin2"].as_int(), 1);
+    Ok(())
+}
+
+#[test]
+fn empty_radix() -> Result<()> {
+    for text in ["a = 0x", "a = 0o", "a = 0b", "a = 0o8", "a = 0b2"] {
+        assert!(from_str(text).is_err(), "{text} should not parse");
+    }
+    Ok(())
+}
+
+#[test]
+fn very_long_literals() -> Result<()> {
+    let digits = "1_".repeat(50_000) + "1";
+    let cases = [
+        format!("a = {digits}"),
+        format!("a = {}", "9".repeat(100_000)),
+        format!("a = {}", "1_".repeat(50_000)),
+        format!("a = 0x{}", "f_".repeat(50_000)),
+        format!("a = 1.{}e5", "1_".repeat(50_000)),
+    ];
+    for text in &cases {
+        // too large or malformed, but rejected without backtracking blowups
+        let start = Instant::now();
+        assert!(from_str(text).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+    let start = Instant::now();
+    let root = from_str(&format!("a = 1.{}", "1".repeat(100_000)))?;
+    assert!((root["a"].as_float() - 1.111).abs() < 0.001);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    Ok(())
+}
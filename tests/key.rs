@@ -121,6 +121,7 @@ fn dotted_key_3() -> toml::Result<()> {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn dotted_key_4() -> toml::Result<()> {
     let text = r#"site."google.com" = true"#;
     let root = from_str(text)?;
@@ -177,6 +178,7 @@ spelling = "favorite"
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn redefined_key_3() -> toml::Result<()> {
     let text = r#"
 # This makes the key "fruit" into a table.
@@ -262,3 +264,102 @@ fn misleading_float_dotted_key() -> toml::Result<()> {
     assert_eq!(root["3"]["14159"].as_str(), "pi");
     Ok(())
 }
+
+#[test]
+fn multiline_basic_key() -> toml::Result<()> {
+    let text = r#""""key""" = "value""#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn multiline_literal_key() -> toml::Result<()> {
+    let text = r#"'''key''' = "value""#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn multiline_table_key() -> toml::Result<()> {
+    let text = r#"[a."""b"""]"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn dotted_key_segment_boundaries() -> toml::Result<()> {
+    let text = r#"
+site."google.com" = { visits = 1 }
+site.google.com = 2
+site.google.org = 3
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["site"]["google.com"]["visits"].as_int(), 1);
+    assert_eq!(root["site"]["google"]["com"].as_int(), 2);
+    assert_eq!(root["site"]["google"]["org"].as_int(), 3);
+    Ok(())
+}
+
+#[test]
+fn escaped_control_in_quoted_key() -> toml::Result<()> {
+    let text = r#""\u0000\t" = 1"#;
+    let root = from_str(text)?;
+    assert_eq!(root["\u{0}\t"].as_int(), 1);
+    Ok(())
+}
+
+#[test]
+fn raw_control_in_literal_key() -> toml::Result<()> {
+    let text = "'a\u{1}b' = 1";
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn duplicate_key_path() -> toml::Result<()> {
+    let cases = [
+        ("name = 1\nname = 2", "name"),
+        ("[fruit]\napple = 1\napple = 2", "fruit.apple"),
+        (
+            "[fruit]\napple.color = 1\napple.color = 2",
+            "fruit.apple.color",
+        ),
+        (
+            "[[fruits]]\n[[fruits]]\nname = 1\nname = 2",
+            "fruits.1.name",
+        ),
+        ("point = { x = 1, x = 2 }", "point.x"),
+        (
+            "[a]\np = [{ x = 1 }, { y = { z = 1, z = 2 } }]",
+            "a.p.1.y.z",
+        ),
+        ("\"a.b\" = 1\n\"a.b\" = 2", "\"a.b\""),
+        ("[a]\n[a]", "a"),
+        ("[a.\"b.c\"]\nx = 1\n[a.\"b.c\"]", "a.\"b.c\""),
+        ("[[servers]]\n[servers]", "servers"),
+        ("[[fruits]]\n[fruits.x]\n[fruits.x]", "fruits.0.x"),
+    ];
+    for (text, path) in cases {
+        let err = from_str(text).unwrap_err();
+        assert_eq!(err, toml::Error::DuplicateKey(path.into()));
+        assert_eq!(err.to_string(), format!("duplicate key `{path}`"));
+    }
+    Ok(())
+}
+
+#[test]
+fn literal_key_with_backslashes() -> toml::Result<()> {
+    let text = r#"'C:\path' = 1
+'a\b'.'\n' = 2
+[ 'x\"y' ]
+z = 3"#;
+    let root = from_str(text)?;
+    assert_eq!(root[r"C:\path"].as_int(), 1);
+    assert_eq!(root[r"a\b"][r"\n"].as_int(), 2);
+    assert_eq!(root[r#"x\"y"#]["z"].as_int(), 3);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use toml::from_str;
+use toml::{from_str, Error, Position};
 
 #[test]
 fn basic() -> toml::Result<()> {
@@ -23,3 +23,72 @@ fn missing_trailing_newline() -> toml::Result<()> {
     assert!(root.is_err());
     Ok(())
 }
+
+#[test]
+fn value_on_next_line() -> toml::Result<()> {
+    let text = "key =\n\"value\"";
+    let root = from_str(text);
+    assert_eq!(
+        root,
+        Err(Error::MissingValue(Position { line: 1, column: 6 }))
+    );
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn trailing_whitespace() -> toml::Result<()> {
+    let text =
+        "str = \"value\"   \nint = 1\t\t\ncrlf = 'value' \t\r\narr = [1, 2]  \nlast = true \t";
+    let root = from_str(text)?;
+    assert_eq!(root["str"].as_str(), "value");
+    assert_eq!(root["int"].as_int(), 1);
+    assert_eq!(root["crlf"].as_str(), "value");
+    assert_eq!(root["arr"].as_arr().len(), 2);
+    assert_eq!(root["last"].as_bool(), true);
+    Ok(())
+}
+
+#[test]
+fn key_with_unquoted_space() -> toml::Result<()> {
+    let err = from_str("foo bar = 1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected `=` or `.`, found key `bar` (line 1, column 5)"
+    );
+    assert_eq!(
+        from_str("point = { x y = 1 }"),
+        Err(Error::ExpectedEquals {
+            found: "y".into(),
+            position: Position {
+                line: 1,
+                column: 13
+            },
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn lone_value_at_top_level() -> toml::Result<()> {
+    let cases = [
+        ("\"just a string\"\n", "\"just a string\"", 1, 16),
+        ("a = 1\n42\n", "42", 2, 3),
+        ("a = 1\ntrue", "true", 2, 5),
+    ];
+    for (text, key, line, column) in cases {
+        let err = from_str(text).unwrap_err();
+        assert_eq!(
+            err,
+            Error::MissingEquals {
+                key: key.into(),
+                position: Position { line, column },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("expected `=` after key `{key}` (line {line}, column {column})")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use toml::{from_str, toml, Result, Value};
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn nested_document() -> Result<()> {
+    let max_connections = 5000;
+    let value = toml! {
+        title = "TOML Example",
+        owner = {
+            name = "Tom Preston-Werner",
+            dob = from_str("dob = 1979-05-27T07:32:00-08:00")?["dob"].clone(),
+        },
+        database = {
+            enabled = true,
+            ports = [8000, 8001, 8002],
+            data = [["delta", "phi"], [3.14]],
+            temp_targets = { cpu = 79.5, "case" = -72.0 },
+            "max-connections" = max_connections,
+        },
+        servers = {
+            alpha = { ip = "10.0.0.1", role = "frontend" },
+        },
+        products = [
+            { name = "Hammer", sku = 738594937 },
+            {},
+        ],
+        empty = [],
+    };
+    let text = r#"
+title = "TOML Example"
+empty = []
+
+[owner]
+name = "Tom Preston-Werner"
+dob = 1979-05-27T07:32:00-08:00
+
+[database]
+enabled = true
+ports = [8000, 8001, 8002]
+data = [["delta", "phi"], [3.14]]
+temp_targets = { cpu = 79.5, case = -72.0 }
+max-connections = 5000
+
+[servers.alpha]
+ip = "10.0.0.1"
+role = "frontend"
+
+[[products]]
+name = "Hammer"
+sku = 738594937
+
+[[products]]
+"#;
+    assert_eq!(value, from_str(text)?);
+    Ok(())
+}
+
+#[test]
+fn empty() {
+    assert_eq!(toml! {}, Value::Table(Default::default()));
+}
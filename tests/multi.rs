@@ -0,0 +1,35 @@
+use toml::{from_str_multi, Result};
+
+#[test]
+fn two_documents() -> Result<()> {
+    let text = r#"
+title = "first"
+[owner]
+name = "Tom"
+---
+title = "second"
+"#;
+    let documents = from_str_multi(text, "---")?;
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0]["title"].as_str(), "first");
+    assert_eq!(documents[0]["owner"]["name"].as_str(), "Tom");
+    assert_eq!(documents[1]["title"].as_str(), "second");
+    assert!(documents[1].as_table().get("owner").is_none());
+    Ok(())
+}
+
+#[test]
+fn separator_must_fill_the_line() -> Result<()> {
+    let text = "a = \"---\"\r\n+++\r\nb = 1\r\n";
+    let documents = from_str_multi(text, "+++")?;
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0]["a"].as_str(), "---");
+    assert_eq!(documents[1]["b"].as_int(), 1);
+    Ok(())
+}
+
+#[test]
+fn invalid_document() {
+    let text = "a = 1\n---\nb = \n";
+    assert!(from_str_multi(text, "---").is_err());
+}
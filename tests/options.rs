@@ -0,0 +1,68 @@
+use toml::{from_str_with, Error, ParserOptions, Position, Result};
+
+#[test]
+#[allow(clippy::field_reassign_with_default)]
+fn max_input_bytes() -> Result<()> {
+    let text = "title = \"TOML Example\"\n";
+    let mut options = ParserOptions::default();
+    options.max_input_bytes = Some(8);
+    assert_eq!(
+        from_str_with(text, &options),
+        Err(Error::InputTooLarge {
+            size: text.len(),
+            limit: 8
+        })
+    );
+    options.max_input_bytes = Some(text.len());
+    let root = from_str_with(text, &options)?;
+    assert_eq!(root["title"].as_str(), "TOML Example");
+    Ok(())
+}
+
+#[test]
+fn default_options() -> Result<()> {
+    let text = format!("title = \"TOML Example\"\n{}", "# padding\n".repeat(1000));
+    let root = from_str_with(&text, &ParserOptions::default())?;
+    assert_eq!(root["title"].as_str(), "TOML Example");
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::field_reassign_with_default)]
+fn numeric_bare_keys() -> Result<()> {
+    let text = "3.14 = \"x\"";
+    let root = from_str_with(text, &ParserOptions::default())?;
+    assert_eq!(root["3"]["14"].as_str(), "x");
+
+    let mut options = ParserOptions::default();
+    options.numeric_bare_keys = false;
+    assert_eq!(
+        from_str_with(text, &options),
+        Err(Error::NumericKey {
+            key: "3.14".into(),
+            position: Position { line: 1, column: 1 }
+        })
+    );
+    let root = from_str_with("314 = \"x\"\n\"3\".14 = \"y\"\nv3.14 = \"z\"", &options)?;
+    assert_eq!(root["314"].as_str(), "x");
+    assert_eq!(root["3"]["14"].as_str(), "y");
+    assert_eq!(root["v3"]["14"].as_str(), "z");
+    Ok(())
+}
+
+#[test]
+fn strict_date_times() -> Result<()> {
+    let text = "odt = 1979-05-27t07:32:00z";
+    let mut options = ParserOptions::default();
+    assert!(from_str_with(text, &options).is_ok());
+
+    options.strict_date_times = true;
+    assert_eq!(
+        from_str_with(text, &options),
+        Err(Error::InvalidValue("1979-05-27t07:32:00z".into()))
+    );
+    assert!(from_str_with("odt = 1979-05-27T07:32:00z", &options).is_err());
+    assert!(from_str_with("ldt = 1979-05-27t07:32:00", &options).is_err());
+    assert!(from_str_with("odt = 1979-05-27T07:32:00Z", &options).is_ok());
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use toml::{from_str_prefix_lines, Result};
+
+#[test]
+fn first_lines() -> Result<()> {
+    let text = r#"title = "example"
+ports = [
+  8000,
+  8001,
+]
+[owner]
+name = "Tom"
+this line is not valid TOML
+"#;
+    let root = from_str_prefix_lines(text, 3)?;
+    assert_eq!(root["title"].as_str(), "example");
+    assert_eq!(root["ports"].as_arr().len(), 2);
+    assert!(root["owner"].as_table().is_empty());
+    let root = from_str_prefix_lines(text, 4)?;
+    assert_eq!(root["owner"]["name"].as_str(), "Tom");
+    assert!(from_str_prefix_lines(text, 5).is_err());
+    Ok(())
+}
+
+#[test]
+fn more_lines_than_document() -> Result<()> {
+    let root = from_str_prefix_lines("a = 1\nb = 2", 10)?;
+    assert_eq!(root.as_table().len(), 2);
+    assert!(from_str_prefix_lines("a = 1", 0)?.as_table().is_empty());
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use toml::{from_str, Array, Result, Table, Value};
+#[cfg(feature = "chrono")]
+use toml::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+
+fn section_names(table: &Table) -> Vec<&str> {
+    let mut names: Vec<&str> = table.keys().map(String::as_str).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn table_and_array() -> Result<()> {
+    let text = "
+[a]
+[b]
+";
+    let root = from_str(text)?;
+    assert_eq!(section_names(root.as_table()), vec!["a", "b"]);
+
+    let mut table = Table::new();
+    let array: Array = vec![Value::Integer(1), Value::Integer(2)];
+    table.insert("numbers".into(), Value::Array(array));
+    assert_eq!(Value::Table(table)["numbers"][1].as_int(), 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn chrono_types() -> Result<()> {
+    let text = "
+odt = 1979-05-27T07:32:00Z
+ldt = 1979-05-27T07:32:00
+ld = 1979-05-27
+lt = 07:32:00
+";
+    let root = from_str(text)?;
+    let odt: DateTime<FixedOffset> = root["odt"].as_offset_date_time();
+    let ldt: NaiveDateTime = root["ldt"].as_local_date_time();
+    let ld: NaiveDate = root["ld"].as_local_date();
+    let lt: NaiveTime = root["lt"].as_local_time();
+    assert_eq!(odt.naive_utc(), ldt);
+    assert_eq!(ldt.date(), ld);
+    assert_eq!(ldt.time(), lt);
+    Ok(())
+}
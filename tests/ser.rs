@@ -0,0 +1,129 @@
+use toml::{
+    from_str, ser::quote_key_if_needed, to_string, to_string_with, Result, SerializerOptions, Value,
+};
+
+#[test]
+fn plain_key() {
+    assert_eq!(quote_key_if_needed("key"), "key");
+    assert_eq!(quote_key_if_needed("bare_key"), "bare_key");
+    assert_eq!(quote_key_if_needed("bare-key"), "bare-key");
+    assert_eq!(quote_key_if_needed("1234"), "1234");
+}
+
+#[test]
+fn dotted_key() {
+    assert_eq!(quote_key_if_needed("google.com"), r#""google.com""#);
+    assert_eq!(quote_key_if_needed("127.0.0.1"), r#""127.0.0.1""#);
+}
+
+#[test]
+fn unicode_key() {
+    assert_eq!(quote_key_if_needed("ʎǝʞ"), r#""ʎǝʞ""#);
+}
+
+#[test]
+fn empty_key() {
+    assert_eq!(quote_key_if_needed(""), r#""""#);
+}
+
+#[test]
+fn escaped_key() {
+    assert_eq!(
+        quote_key_if_needed("quoted \"value\""),
+        r#""quoted \"value\"""#
+    );
+    assert_eq!(quote_key_if_needed("tab\there"), r#""tab\there""#);
+}
+
+#[test]
+fn document() -> Result<()> {
+    let text = r#"title = "example"
+
+[owner]
+name = "Tom"
+
+[[products]]
+name = "Hammer"
+
+[[products]]
+name = "Nail"
+
+[servers.alpha]
+ip = "10.0.0.1"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(to_string(&root)?, text);
+    Ok(())
+}
+
+#[test]
+fn document_not_a_table() {
+    assert!(to_string(&Value::Integer(1)).is_err());
+}
+
+#[test]
+fn prefer_inline_threshold() -> Result<()> {
+    let text = r#"
+[point]
+x = 1
+y = 2
+
+[owner]
+name = "Tom"
+dob = 1979-05-27
+city = "San Francisco"
+
+[servers.alpha]
+ip = "10.0.0.1"
+ports = [8000, 8001]
+"#;
+    let root = from_str(text)?;
+    let options = SerializerOptions {
+        prefer_inline_threshold: Some(3),
+    };
+    assert_eq!(
+        to_string_with(&root, &options)?,
+        r#"point = { x = 1, y = 2 }
+
+[owner]
+city = "San Francisco"
+dob = 1979-05-27
+name = "Tom"
+
+[servers.alpha]
+ip = "10.0.0.1"
+ports = [8000, 8001]
+"#
+    );
+    assert_eq!(
+        to_string_with(&root, &SerializerOptions::default())?,
+        to_string(&root)?
+    );
+    Ok(())
+}
+
+#[test]
+fn document_keys() -> Result<()> {
+    let text = r#"
+bare_key = 1
+Key_1 = 2
+"has space" = 3
+"" = 4
+
+["site.com"]
+"quoted \"key\"" = 5
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        to_string(&root)?,
+        r#""" = 4
+Key_1 = 2
+bare_key = 1
+"has space" = 3
+
+["site.com"]
+"quoted \"key\"" = 5
+"#
+    );
+    Ok(())
+}
@@ -0,0 +1,245 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use toml::{from_str_serde, to_string_serde, Result};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    title: String,
+    description: Option<String>,
+    owner: Owner,
+    database: Database,
+    servers: Vec<Server>,
+    log_level: Level,
+    storage: Storage,
+    retry: Retry,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Owner {
+    name: String,
+    dob: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Database {
+    enabled: bool,
+    ports: Vec<u16>,
+    temp_targets: TempTargets,
+    timeout: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct TempTargets {
+    cpu: f64,
+    case: f64,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    name: String,
+    ip: String,
+    role: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Storage {
+    Memory,
+    Disk { path: String, size_mb: u32 },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "kind", content = "params", rename_all = "lowercase")]
+enum Retry {
+    Never,
+    Fixed { delay: u64 },
+}
+
+#[test]
+fn derive() -> Result<()> {
+    let text = r#"
+title = "TOML Example"
+log_level = "info"
+retry = { kind = "fixed", params = { delay = 30 } }
+
+[owner]
+name = "Tom Preston-Werner"
+dob = 1979-05-27T07:32:00-08:00
+
+[database]
+enabled = true
+ports = [ 8000, 8001, 8002 ]
+temp_targets = { cpu = 79.5, case = 72.0 }
+
+[storage.disk]
+path = "/var/lib/data"
+size_mb = 512
+
+[labels]
+env = "prod"
+
+[[servers]]
+name = "alpha"
+ip = "10.0.0.1"
+role = "frontend"
+
+[[servers]]
+name = "beta"
+ip = "10.0.0.2"
+"#;
+    let config: Config = from_str_serde(text)?;
+    assert_eq!(
+        config,
+        Config {
+            title: "TOML Example".into(),
+            description: None,
+            owner: Owner {
+                name: "Tom Preston-Werner".into(),
+                dob: "1979-05-27T07:32:00-08:00".into(),
+            },
+            database: Database {
+                enabled: true,
+                ports: vec![8000, 8001, 8002],
+                temp_targets: TempTargets {
+                    cpu: 79.5,
+                    case: 72.0,
+                },
+                timeout: None,
+            },
+            servers: vec![
+                Server {
+                    name: "alpha".into(),
+                    ip: "10.0.0.1".into(),
+                    role: Some("frontend".into()),
+                },
+                Server {
+                    name: "beta".into(),
+                    ip: "10.0.0.2".into(),
+                    role: None,
+                },
+            ],
+            log_level: Level::Info,
+            storage: Storage::Disk {
+                path: "/var/lib/data".into(),
+                size_mb: 512,
+            },
+            retry: Retry::Fixed { delay: 30 },
+            labels: HashMap::from([("env".into(), "prod".into())]),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn unit_variant() -> Result<()> {
+    let text = r#"
+level = "debug"
+storage = "memory"
+retry = { kind = "never" }
+"#;
+    #[derive(Deserialize)]
+    struct Partial {
+        level: Level,
+        storage: Storage,
+        retry: Retry,
+    }
+    let partial: Partial = from_str_serde(text)?;
+    assert_eq!(partial.level, Level::Debug);
+    assert_eq!(partial.storage, Storage::Memory);
+    assert_eq!(partial.retry, Retry::Never);
+    Ok(())
+}
+
+#[test]
+fn error() {
+    let text = r#"
+port = 70000
+"#;
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Partial {
+        port: u16,
+    }
+    let err = from_str_serde::<Partial>(text).unwrap_err();
+    assert!(err.to_string().contains("70000"));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Manifest {
+    name: String,
+    version: Option<String>,
+    tags: Vec<String>,
+    package: Package,
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Package {
+    edition: u32,
+    publish: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Target {
+    name: String,
+    path: Option<String>,
+}
+
+#[test]
+fn serialize() -> Result<()> {
+    let manifest = Manifest {
+        name: "toml".into(),
+        version: None,
+        tags: vec!["parser".into(), "config".into()],
+        package: Package {
+            edition: 2021,
+            publish: false,
+        },
+        targets: vec![
+            Target {
+                name: "lib".into(),
+                path: Some("src/lib.rs".into()),
+            },
+            Target {
+                name: "bin".into(),
+                path: None,
+            },
+        ],
+    };
+    let text = to_string_serde(&manifest)?;
+    assert_eq!(
+        text,
+        r#"name = "toml"
+tags = ["parser", "config"]
+
+[package]
+edition = 2021
+publish = false
+
+[[targets]]
+name = "lib"
+path = "src/lib.rs"
+
+[[targets]]
+name = "bin"
+"#
+    );
+    assert_eq!(from_str_serde::<Manifest>(&text)?, manifest);
+    Ok(())
+}
+
+#[test]
+fn serialize_non_table() {
+    assert!(to_string_serde(&42).is_err());
+}
@@ -0,0 +1,35 @@
+use toml::{from_str, Error, Result};
+
+#[test]
+fn signed_decimal_zero() -> Result<()> {
+    let text = "
+int1 = +0
+int2 = -0
+flt1 = +0.0
+flt2 = -0.0
+";
+    let root = from_str(text)?;
+    assert_eq!(root["int1"].as_int(), 0);
+    assert_eq!(root["int2"].as_int(), 0);
+    assert_eq!(root["flt1"].as_float(), 0.0);
+    assert!(root["flt1"].as_float().is_sign_positive());
+    assert_eq!(root["flt2"].as_float(), 0.0);
+    assert!(root["flt2"].as_float().is_sign_negative());
+    Ok(())
+}
+
+#[test]
+fn signed_radix_zero() {
+    for value in ["+0x0", "-0x0", "+0o0", "-0o0", "+0b0", "-0b0"] {
+        let text = format!("a = {value}");
+        assert_eq!(from_str(&text), Err(Error::InvalidValue(value.into())));
+    }
+}
+
+#[test]
+fn signed_leading_zero() {
+    for value in ["+00", "-01"] {
+        let text = format!("a = {value}");
+        assert_eq!(from_str(&text), Err(Error::InvalidValue(value.into())));
+    }
+}
@@ -1,4 +1,4 @@
-use toml::{from_str, Result};
+use toml::{from_str, Error, Position, Result};
 
 #[test]
 fn basic_string() -> Result<()> {
@@ -140,6 +140,14 @@ str7 = """"This," she said, "is just a pointless statement.""""
     Ok(())
 }
 
+#[test]
+fn multiline_non_ascii() -> Result<()> {
+    let text = "str = \"\"\"café \\\"naïve\\\" 😀\"\"\"";
+    let root = from_str(text)?;
+    assert_eq!(root["str"].as_str(), "café \"naïve\" 😀");
+    Ok(())
+}
+
 #[test]
 fn literal() -> Result<()> {
     let text = r#"
@@ -213,3 +221,189 @@ fn literal_multiline_4() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn literal_multiline_line_ending_slash() -> Result<()> {
+    let text = r#"str = '''
+The quick brown \
+  fox jumps over \
+    the lazy dog.'''
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root["str"].as_str(),
+        "The quick brown \\\n  fox jumps over \\\n    the lazy dog."
+    );
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<()> {
+    let text = r#"
+basic = ""
+literal = ''
+multiline_basic = """"""
+multiline_literal = ''''''
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["basic"].as_str(), "");
+    assert_eq!(root["literal"].as_str(), "");
+    assert_eq!(root["multiline_basic"].as_str(), "");
+    assert_eq!(root["multiline_literal"].as_str(), "");
+    Ok(())
+}
+
+#[test]
+fn multiple_multiline() -> Result<()> {
+    let text = r#"
+str1 = """Roses are red"""
+str2 = """Violets are blue"""
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["str1"].as_str(), "Roses are red");
+    assert_eq!(root["str2"].as_str(), "Violets are blue");
+    Ok(())
+}
+
+#[test]
+fn multiline_unterminated() -> Result<()> {
+    let text = r#"
+str1 = """Roses are red
+Violets are blue
+"#;
+    let root = from_str(text);
+    assert_eq!(
+        root,
+        Err(Error::UnterminatedString(Position { line: 2, column: 8 }))
+    );
+    Ok(())
+}
+
+#[test]
+fn literal_multiline_unterminated() -> Result<()> {
+    let text = r#"
+regex = '''I [dw]on't need \d{2} apples''
+"#;
+    let root = from_str(text);
+    assert_eq!(
+        root,
+        Err(Error::UnterminatedString(Position { line: 2, column: 9 }))
+    );
+    Ok(())
+}
+
+#[test]
+fn multiline_invalid_escape() -> Result<()> {
+    let root = from_str(r#"x = """bad \q escape""""#);
+    assert_eq!(
+        root,
+        Err(Error::InvalidEscape {
+            escape: r"\q".into(),
+            position: Position {
+                line: 1,
+                column: 12
+            },
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn literal_multiline_closed_control_character() -> Result<()> {
+    let root = from_str("x = '''a\u{0}b'''");
+    assert_eq!(
+        root,
+        Err(Error::ControlCharacter {
+            character: '\u{0}',
+            position: Position { line: 1, column: 9 },
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn literal_control_character() -> Result<()> {
+    let root = from_str("str = 'nul \u{0}'");
+    assert_eq!(
+        root,
+        Err(Error::ControlCharacter {
+            character: '\u{0}',
+            position: Position {
+                line: 1,
+                column: 12
+            },
+        })
+    );
+    let root = from_str("str = 'del \u{7F}'");
+    assert_eq!(
+        root,
+        Err(Error::ControlCharacter {
+            character: '\u{7F}',
+            position: Position {
+                line: 1,
+                column: 12
+            },
+        })
+    );
+    let root = from_str("str = 'tab \t'")?;
+    assert_eq!(root["str"].as_str(), "tab \t");
+    Ok(())
+}
+
+#[test]
+fn literal_multiline_control_character() -> Result<()> {
+    let root = from_str("str = '''\nnul \u{0}\n'''");
+    assert_eq!(
+        root,
+        Err(Error::ControlCharacter {
+            character: '\u{0}',
+            position: Position { line: 2, column: 5 },
+        })
+    );
+    let root = from_str("str = '''\nbare cr \r here'''");
+    assert_eq!(
+        root,
+        Err(Error::ControlCharacter {
+            character: '\r',
+            position: Position { line: 2, column: 9 },
+        })
+    );
+    let root = from_str("str = '''\ntab \t\r\nnewline'''")?;
+    assert_eq!(root["str"].as_str(), "tab \t\r\nnewline");
+    Ok(())
+}
+
+#[test]
+fn unicode_escapes() -> Result<()> {
+    let root = from_str(r#"str = "café \U0001F600""#)?;
+    assert_eq!(root["str"].as_str(), "café 😀");
+    let root = from_str("str = \"\"\"\n\\u00E9\\\\n\"\"\"")?;
+    assert_eq!(root["str"].as_str(), "é\\n");
+    let root = from_str(r#"str = "\uD800""#);
+    assert!(root.is_err());
+    let root = from_str(r#"str = "\U00110000""#);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn closing_delimiter_with_extra_quotes() -> Result<()> {
+    let root = from_str(r#"str = """a"""""#)?;
+    assert_eq!(root["str"].as_str(), "a\"");
+    let root = from_str(r#"str = """a""""""#)?;
+    assert_eq!(root["str"].as_str(), "a\"\"");
+    let root = from_str(r#"str = """""""""#)?;
+    assert_eq!(root["str"].as_str(), "\"\"");
+    // only two extra quotes belong to the content, so the sixth is left over
+    let root = from_str(r#"str = """a"""""""#);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn crlf_after_opening_delimiter() -> Result<()> {
+    let root = from_str("basic = \"\"\"\r\nfirst\r\nsecond\"\"\"\r\nliteral = '''\r\nfirst'''")?;
+    assert_eq!(root["basic"].as_str(), "first\r\nsecond");
+    assert_eq!(root["literal"].as_str(), "first");
+    Ok(())
+}
@@ -1,5 +1,6 @@
+#[cfg(feature = "chrono")]
 use chrono::NaiveDate;
-use toml::{from_str, Result};
+use toml::{from_str, Error, Result};
 
 #[test]
 fn table() -> Result<()> {
@@ -133,6 +134,7 @@ fn out_of_order_2() -> Result<()> {
 }
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn top_level_table() -> Result<()> {
     let text = r#"
 # Top-level table begins.
@@ -148,14 +150,21 @@ member_since = 1999-08-04
     assert_eq!(root["name"].as_str(), "Fido");
     assert_eq!(root["breed"].as_str(), "pug");
     assert_eq!(root["owner"]["name"].as_str(), "Regina Dogman");
+    #[cfg(feature = "chrono")]
     assert_eq!(
         root["owner"]["member_since"].as_local_date(),
         NaiveDate::from_ymd_opt(1999, 08, 04).unwrap()
     );
+    #[cfg(not(feature = "chrono"))]
+    assert_eq!(
+        root["owner"]["member_since"].as_local_date().as_str(),
+        "1999-08-04"
+    );
     Ok(())
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn dotted_key() -> Result<()> {
     let text = r#"
 fruit.apple.color = "red"
@@ -173,6 +182,7 @@ fruit.apple.taste.sweet = true
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn redefine_3() -> Result<()> {
     let text = r#"
 [fruit]
@@ -207,8 +217,122 @@ fn redefine_5() -> Result<()> {
     let text = r#"
 [fruit]
 [fruit]
+"#;
+    let root = from_str(text);
+    assert_eq!(root, Err(Error::DuplicateKey("fruit".into())));
+    Ok(())
+}
+
+#[test]
+fn many_tables() -> Result<()> {
+    let mut text = String::new();
+    for i in 0..1000 {
+        text.push_str(&format!("[table-{i}.sub]\nkey = {i}\n\n"));
+    }
+    let root = from_str(&text)?;
+    assert_eq!(root.as_table().len(), 1000);
+    assert_eq!(root["table-0"]["sub"]["key"].as_int(), 0);
+    assert_eq!(root["table-999"]["sub"]["key"].as_int(), 999);
+    Ok(())
+}
+
+#[test]
+fn dotted_table_at_eof() -> Result<()> {
+    let text = "key = 1\n[a.b.c]";
+    let root = from_str(text)?;
+    assert!(root["a"]["b"]["c"].as_table().is_empty());
+    Ok(())
+}
+
+#[test]
+fn trailing_dot() -> Result<()> {
+    let text = r#"
+[a.]
+key = 1
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn leading_dot() -> Result<()> {
+    let text = r#"
+[.a]
+key = 1
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn empty_segment() -> Result<()> {
+    let text = r#"
+[a..b]
+key = 1
+"#;
+    let root = from_str(text);
+    assert!(root.is_err());
+    Ok(())
+}
+
+#[test]
+fn key_value_on_header_line() -> Result<()> {
+    let text = r#"
+[table] key = 1
 "#;
     let root = from_str(text);
     assert!(root.is_err());
     Ok(())
 }
+
+#[test]
+fn trailing_whitespace_after_header() -> Result<()> {
+    let text = "[table]  \t\nkey = 1\n[[array]] \t\r\nkey = 2\n[end] \t";
+    let root = from_str(text)?;
+    assert_eq!(root["table"]["key"].as_int(), 1);
+    assert_eq!(root["array"][0]["key"].as_int(), 2);
+    assert!(root["end"].as_table().is_empty());
+    Ok(())
+}
+
+#[test]
+fn mixed_quoting_in_header() -> Result<()> {
+    let text = r#"
+[a."b".'c'.d]
+key = 1
+
+[a."b.c".'d.e']
+key = 2
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["a"]["b"]["c"]["d"]["key"].as_int(), 1);
+    assert_eq!(root["a"]["b.c"]["d.e"]["key"].as_int(), 2);
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn header_after_dotted_key() -> Result<()> {
+    // a header may add a subtable to a table defined by a dotted key
+    let root = from_str("fruit.apple.color = \"red\"\n[fruit.apple.texture]\nsmooth = true")?;
+    assert_eq!(root["fruit"]["apple"]["color"].as_str(), "red");
+    assert_eq!(root["fruit"]["apple"]["texture"]["smooth"].as_bool(), true);
+
+    let root = from_str("[fruit]\napple.color = \"red\"\n[fruit.apple.texture]")?;
+    assert!(root["fruit"]["apple"]["texture"].as_table().is_empty());
+
+    // but may not reopen one, or turn a value into a table
+    for text in [
+        "fruit.apple = 1\n[fruit]",
+        "fruit.apple.color = \"red\"\n[fruit]",
+        "fruit.apple.color = \"red\"\n[fruit.apple]",
+        "fruit.apple = 1\n[fruit.apple]",
+        "[t1]\nt2.t3.v = 0\n[t1.t2]",
+        "[t1]\nt2.t3.v = 0\n[t1.t2.t3]",
+    ] {
+        assert!(from_str(text).is_err(), "{text:?} should be rejected");
+    }
+    Ok(())
+}
@@ -0,0 +1,891 @@
+use toml::{from_str, Error, LineEnding, NanPolicy, Result, Value, ValueKind};
+
+#[test]
+fn retain() -> Result<()> {
+    let text = r#"
+name = "Fido"
+age = 3
+breed = "pug"
+owner = { name = "Regina Dogman" }
+"#;
+    let mut root = from_str(text)?;
+    root.retain(|_, value| !matches!(value, Value::String(_)));
+    assert_eq!(root.as_table().len(), 2);
+    assert_eq!(root["age"].as_int(), 3);
+    assert_eq!(root["owner"]["name"].as_str(), "Regina Dogman");
+    Ok(())
+}
+
+#[test]
+fn retain_not_table() -> Result<()> {
+    let mut value = Value::Integer(1);
+    value.retain(|_, _| false);
+    assert_eq!(value, Value::Integer(1));
+    Ok(())
+}
+
+#[test]
+fn pointer() -> Result<()> {
+    let text = r#"
+[[servers]]
+host = "alpha"
+
+[[servers]]
+host = "beta"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.pointer("servers.1.host"),
+        Some(&Value::String("beta".into()))
+    );
+    assert_eq!(root.pointer("servers.2.host"), None);
+    assert_eq!(root.pointer("servers.0.host.name"), None);
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn require_present() -> Result<()> {
+    let text = r#"
+[database]
+host = "localhost"
+port = 5432
+enabled = true
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root.require_str("database.host")?, "localhost");
+    assert_eq!(root.require_int("database.port")?, 5432);
+    assert_eq!(root.require_bool("database.enabled")?, true);
+    assert_eq!(root.require_table("database")?.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn require_absent() -> Result<()> {
+    let text = r#"
+[database]
+host = "localhost"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.require_int("database.port"),
+        Err(Error::MissingKey("database.port".into()))
+    );
+    Ok(())
+}
+
+#[test]
+fn require_wrong_type() -> Result<()> {
+    let text = r#"
+[database]
+port = "5432"
+"#;
+    let root = from_str(text)?;
+    let err = root.require_int("database.port").unwrap_err();
+    assert_eq!(
+        err,
+        Error::WrongType {
+            path: "database.port".into(),
+            expected: "integer",
+            found: "string",
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "expected integer at `database.port`, found string"
+    );
+    Ok(())
+}
+
+#[test]
+fn sorted_entries() -> Result<()> {
+    let text = r#"
+zebra = 1
+apple = 2
+mango = 3
+Banana = 4
+"#;
+    let root = from_str(text)?;
+    let keys: Vec<&str> = root
+        .sorted_entries()
+        .into_iter()
+        .map(|(key, _)| key.as_str())
+        .collect();
+    assert_eq!(keys, vec!["Banana", "apple", "mango", "zebra"]);
+    assert_eq!(root.sorted_entries()[0].1.as_int(), 4);
+    Ok(())
+}
+
+#[test]
+fn sorted_entries_not_table() -> Result<()> {
+    assert!(Value::Integer(1).sorted_entries().is_empty());
+    assert!(Value::Array(vec![Value::Bool(true)])
+        .sorted_entries()
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn append_array() -> Result<()> {
+    let text = r#"
+paths = ["/usr/lib", "/lib"]
+name = "loader"
+"#;
+    let mut root = from_str(text)?;
+    root["paths"].append_array(vec![Value::String("/opt/lib".into())])?;
+    assert_eq!(root["paths"].as_arr().len(), 3);
+    assert_eq!(root["paths"][2].as_str(), "/opt/lib");
+
+    let err = root["name"].append_array(vec![]).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnexpectedType {
+            expected: "array",
+            found: "string",
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn is_homogeneous_array() -> Result<()> {
+    let text = r#"
+integers = [1, 2, 3]
+mixed = [1, "a"]
+numbers = [1, 1.0]
+empty = []
+tables = [{ x = 1 }, { y = "a" }]
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["integers"].is_homogeneous_array(), Some(true));
+    assert_eq!(root["mixed"].is_homogeneous_array(), Some(false));
+    assert_eq!(root["numbers"].is_homogeneous_array(), Some(false));
+    assert_eq!(root["empty"].is_homogeneous_array(), Some(true));
+    assert_eq!(root["tables"].is_homogeneous_array(), Some(true));
+    assert_eq!(root.is_homogeneous_array(), None);
+    Ok(())
+}
+
+#[test]
+fn into_entries() -> Result<()> {
+    let text = r#"
+name = "Fido"
+age = 3
+"#;
+    let root = from_str(text)?;
+    let keys: Vec<_> = root.entries().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["name", "age"]);
+    let mut entries = Vec::new();
+    for (key, value) in root.into_entries() {
+        entries.push((key, value));
+    }
+    assert_eq!(
+        entries,
+        [
+            ("name".to_string(), Value::String("Fido".into())),
+            ("age".to_string(), Value::Integer(3)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn into_iter_array() -> Result<()> {
+    let root = from_str("ports = [8000, 8001]")?;
+    let mut ports = Vec::new();
+    for port in &root["ports"] {
+        ports.push(port.as_int());
+    }
+    assert_eq!(ports, [8000, 8001]);
+    let ports: Vec<_> = root["ports"].clone().into_iter().collect();
+    assert_eq!(ports, [Value::Integer(8000), Value::Integer(8001)]);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "not an array value")]
+fn into_iter_table() {
+    let root = from_str("name = \"Fido\"").unwrap();
+    for _ in root {}
+}
+
+#[test]
+#[should_panic(expected = "not a table value")]
+fn into_entries_scalar() {
+    for _ in Value::Integer(1).into_entries() {}
+}
+
+#[test]
+fn get_table_or_empty() -> Result<()> {
+    let text = r#"
+logging = "verbose"
+
+[database]
+port = 5432
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root.get_table_or_empty("database")["port"].as_int(), 5432);
+    assert!(root.get_table_or_empty("server").as_table().is_empty());
+    assert!(root.get_table_or_empty("logging").as_table().is_empty());
+    Ok(())
+}
+
+#[test]
+fn kind() -> Result<()> {
+    let text = r#"
+name = "Fido"
+age = 3
+weight = 7.5
+good = true
+tags = ["pug"]
+owner = { name = "Regina Dogman" }
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root.kind(), ValueKind::Table);
+    assert_eq!(root["name"].kind(), ValueKind::String);
+    assert_eq!(root["age"].kind(), ValueKind::Integer);
+    assert_eq!(root["weight"].kind(), ValueKind::Float);
+    assert_eq!(root["good"].kind(), ValueKind::Bool);
+    assert_eq!(root["tags"].kind(), ValueKind::Array);
+    assert_eq!(root["owner"].kind(), ValueKind::Table);
+    let scalars = root
+        .as_table()
+        .values()
+        .filter(|value| !matches!(value.kind(), ValueKind::Array | ValueKind::Table))
+        .count();
+    assert_eq!(scalars, 4);
+    Ok(())
+}
+
+#[test]
+fn get_path() -> Result<()> {
+    let text = r#"
+[site]
+"google.com" = { visits = 1 }
+
+[[servers]]
+host = "alpha"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.get_path(["site", "google.com", "visits"]),
+        Some(&Value::Integer(1))
+    );
+    assert_eq!(root.pointer("site.google.com.visits"), None);
+    assert_eq!(
+        root.get_path(["servers", "0", "host"]),
+        Some(&Value::String("alpha".into()))
+    );
+    assert_eq!(root.get_path(["site", "google"]), None);
+    Ok(())
+}
+
+#[test]
+fn count_leaves_and_max_depth() -> Result<()> {
+    let text = r#"
+title = "example"
+ports = [8000, 8001]
+empty = []
+
+[owner]
+name = "Tom"
+address = { city = "San Francisco", zip = [9, 4, 1] }
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root.count_leaves(), 8);
+    assert_eq!(root.max_depth(), 4);
+    assert_eq!(root["ports"].max_depth(), 1);
+    assert_eq!(root["empty"].max_depth(), 1);
+    assert_eq!(root["title"].max_depth(), 0);
+    assert_eq!(root["title"].count_leaves(), 1);
+    assert_eq!(from_str("")?.max_depth(), 1);
+    Ok(())
+}
+
+#[test]
+fn key_at_and_value_at() -> Result<()> {
+    let text = r#"
+zebra = 1
+apple = 2
+owner.name = "Tom"
+mango = { b = 1, a = 2 }
+
+[table]
+y = 1
+x = 2
+"#;
+    let root = from_str(text)?;
+    let keys: Vec<_> = (0..5).map_while(|i| root.key_at(i)).collect();
+    assert_eq!(keys, ["zebra", "apple", "owner", "mango", "table"]);
+    assert_eq!(root.value_at(1), Some(&Value::Integer(2)));
+    assert_eq!(root["mango"].key_at(0), Some("b"));
+    assert_eq!(root["table"].key_at(1), Some("x"));
+    assert_eq!(root.key_at(5), None);
+    assert_eq!(root["zebra"].value_at(0), None);
+    Ok(())
+}
+
+#[test]
+fn get_ci() -> Result<()> {
+    let text = r#"
+database = "postgres"
+Port = 1
+PORT = 2
+port = 3
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.get_ci("Database"),
+        Some(&Value::String("postgres".into()))
+    );
+    assert_eq!(root.get_ci("DATABASE"), root.get_ci("database"));
+    assert_eq!(root.get_ci("port"), Some(&Value::Integer(3)));
+    assert_eq!(root.get_ci("pOrT"), Some(&Value::Integer(1)));
+    assert_eq!(root.get_ci("databases"), None);
+    assert_eq!(root["database"].get_ci("database"), None);
+    Ok(())
+}
+
+#[test]
+fn truncate_strings() -> Result<()> {
+    let text = r#"
+short = "abc"
+exact = "abcde"
+long = "abcdefgh"
+unicode = "ʎǝʞʎǝʞ"
+nested = { list = ["abcdefgh", 12345678], table = { key = "abcdefgh" } }
+"#;
+    let mut root = from_str(text)?;
+    root.truncate_strings(5);
+    assert_eq!(root["short"].as_str(), "abc");
+    assert_eq!(root["exact"].as_str(), "abcde");
+    assert_eq!(root["long"].as_str(), "abcde...");
+    assert_eq!(root["unicode"].as_str(), "ʎǝʞʎǝ...");
+    assert_eq!(root["nested"]["list"][0].as_str(), "abcde...");
+    assert_eq!(root["nested"]["list"][1].as_int(), 12345678);
+    assert_eq!(root["nested"]["table"]["key"].as_str(), "abcde...");
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn merge_str() -> Result<()> {
+    let defaults = r#"
+title = "example"
+ports = [8000, 8001]
+
+[database]
+host = "localhost"
+port = 5432
+
+[logging]
+level = "info"
+"#;
+    let overrides = r#"
+ports = [9000]
+
+[database]
+port = 6543
+user = "admin"
+
+[cache]
+enabled = true
+"#;
+    let mut root = from_str(defaults)?;
+    root.merge_str(overrides)?;
+    assert_eq!(root["title"].as_str(), "example");
+    assert_eq!(root["ports"].as_arr(), &[Value::Integer(9000)]);
+    assert_eq!(root["database"]["host"].as_str(), "localhost");
+    assert_eq!(root["database"]["port"].as_int(), 6543);
+    assert_eq!(root["database"]["user"].as_str(), "admin");
+    assert_eq!(root["logging"]["level"].as_str(), "info");
+    assert_eq!(root["cache"]["enabled"].as_bool(), true);
+    assert!(root.merge_str("invalid =").is_err());
+    Ok(())
+}
+
+#[test]
+fn checked_ints() -> Result<()> {
+    let text = r#"
+port = 8080
+negative = -1
+large = 70000
+huge = 9223372036854775807
+name = "localhost"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["port"].as_u16()?, 8080);
+    assert_eq!(root["large"].as_u32()?, 70000);
+    assert_eq!(root["huge"].as_u64()?, 9223372036854775807);
+    assert_eq!(root["port"].as_usize()?, 8080);
+    assert_eq!(root["negative"].as_i32()?, -1);
+    let err = root["negative"].as_u16().unwrap_err();
+    assert_eq!(
+        err,
+        Error::OutOfRange {
+            value: -1,
+            target: "u16"
+        }
+    );
+    assert_eq!(err.to_string(), "integer -1 is out of range for u16");
+    assert!(root["large"].as_u16().is_err());
+    assert!(root["port"].as_u8().is_err());
+    assert!(root["huge"].as_i32().is_err());
+    assert_eq!(
+        root["name"].as_u16(),
+        Err(Error::UnexpectedType {
+            expected: "integer",
+            found: "string"
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn first_and_last() -> Result<()> {
+    let text = r#"
+ports = [8000, 8001, 8002]
+empty = []
+name = "alpha"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root["ports"].first(), Some(&Value::Integer(8000)));
+    assert_eq!(root["ports"].last(), Some(&Value::Integer(8002)));
+    assert_eq!(root["empty"].first(), None);
+    assert_eq!(root["empty"].last(), None);
+    assert_eq!(root["name"].first(), None);
+    assert_eq!(root.last(), None);
+    Ok(())
+}
+
+#[test]
+fn at() -> Result<()> {
+    let text = r#"
+[database]
+host = "localhost"
+
+[[servers]]
+name = "alpha"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.at("database").at("host").get(),
+        Some(&Value::String("localhost".into()))
+    );
+    assert_eq!(
+        root.at("servers").at_index(0).at("name").get(),
+        Some(&Value::String("alpha".into()))
+    );
+    assert_eq!(root.at("database").at("port").get(), None);
+    assert_eq!(root.at("cache").at("size").at("max").get(), None);
+    assert_eq!(root.at("servers").at_index(1).at("name").get(), None);
+    assert_eq!(root.at("database").at("host").at("name").get(), None);
+    assert_eq!(root.at("servers").at("name").get(), None);
+    Ok(())
+}
+
+#[test]
+fn flatten() -> Result<()> {
+    let text = r#"
+title = "example"
+empty = {}
+
+[database]
+ports = [8000, 8001]
+connection.timeout = 30
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.flatten(),
+        [
+            ("title".to_string(), &Value::String("example".into())),
+            (
+                "database.ports".to_string(),
+                &Value::Array(vec![Value::Integer(8000), Value::Integer(8001)])
+            ),
+            (
+                "database.connection.timeout".to_string(),
+                &Value::Integer(30)
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn to_env_vars() -> Result<()> {
+    let text = r#"
+title = "example"
+
+[database]
+host = "localhost"
+ports = [8000, 8001]
+max-connections = 10
+enabled = true
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.to_env_vars("app"),
+        [
+            ("APP_TITLE".to_string(), "example".to_string()),
+            ("APP_DATABASE_HOST".to_string(), "localhost".to_string()),
+            ("APP_DATABASE_PORTS".to_string(), "[8000, 8001]".to_string()),
+            ("APP_DATABASE_MAX_CONNECTIONS".to_string(), "10".to_string()),
+            ("APP_DATABASE_ENABLED".to_string(), "true".to_string()),
+        ]
+    );
+    assert_eq!(root.to_env_vars("")[0].0, "TITLE");
+    Ok(())
+}
+
+const NON_FINITE: &str = r#"
+ratio = 0.5
+top = inf
+
+[limits]
+lower = -inf
+values = [1.0, nan, 2.0]
+"#;
+
+#[test]
+fn sanitize_for_json_null() -> Result<()> {
+    let mut root = from_str(NON_FINITE)?;
+    root.sanitize_for_json(NanPolicy::Null)?;
+    let expected = from_str(
+        r#"
+ratio = 0.5
+
+[limits]
+values = [1.0, 2.0]
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn sanitize_for_json_error() -> Result<()> {
+    let mut root = from_str(NON_FINITE)?;
+    assert_eq!(
+        root.sanitize_for_json(NanPolicy::Error),
+        Err(Error::Custom(
+            "`inf` at `top` cannot be represented in JSON".into()
+        ))
+    );
+    let mut root = from_str("[limits]\nvalues = [1.0, nan]")?;
+    assert_eq!(
+        root.sanitize_for_json(NanPolicy::Error),
+        Err(Error::Custom(
+            "`nan` at `limits.values.1` cannot be represented in JSON".into()
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn sanitize_for_json_string() -> Result<()> {
+    let mut root = from_str(NON_FINITE)?;
+    root.sanitize_for_json(NanPolicy::String)?;
+    let expected = from_str(
+        r#"
+ratio = 0.5
+top = "inf"
+
+[limits]
+lower = "-inf"
+values = [1.0, "nan", 2.0]
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn set_path() -> Result<()> {
+    let text = r#"
+[server]
+host = "localhost"
+ports = [8000, 8001]
+"#;
+    let mut root = from_str(text)?;
+    root.set_path("server.host", Value::String("example.com".into()))?;
+    root.set_path("server.tls.cert.path", Value::String("cert.pem".into()))?;
+    root.set_path("server.ports.1", Value::Integer(9000))?;
+    root.set_path("debug", Value::Bool(true))?;
+
+    let expected = from_str(
+        r#"
+debug = true
+
+[server]
+host = "example.com"
+ports = [8000, 9000]
+tls.cert.path = "cert.pem"
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn set_path_conflict() -> Result<()> {
+    let mut root = from_str("[server]\nhost = \"localhost\"\nports = [8000]")?;
+    assert_eq!(
+        root.set_path("server.host.name", Value::Integer(1)),
+        Err(Error::WrongType {
+            path: "server.host".into(),
+            expected: "table",
+            found: "string",
+        })
+    );
+    assert_eq!(
+        root.set_path("server.ports.3", Value::Integer(1)),
+        Err(Error::MissingKey("server.ports.3".into()))
+    );
+    assert_eq!(
+        root,
+        from_str("[server]\nhost = \"localhost\"\nports = [8000]")?
+    );
+    Ok(())
+}
+
+#[test]
+fn get_or() -> Result<()> {
+    let root = from_str("port = 8080")?;
+    let default = Value::Integer(80);
+    assert_eq!(root.get_or("port", &default).as_int(), 8080);
+    assert_eq!(root.get_or("timeout", &default).as_int(), 80);
+    assert_eq!(root["port"].get_or("port", &default).as_int(), 80);
+    Ok(())
+}
+
+#[test]
+fn dedup_array() -> Result<()> {
+    let text = r#"
+include = ["/usr/lib", "/lib", "/usr/lib", "/opt/lib", "/lib"]
+numbers = [1, 1.0, 2, 1]
+points = [{ x = 1, y = 2 }, { x = 2, y = 1 }, { y = 2, x = 1 }]
+name = "loader"
+"#;
+    let mut root = from_str(text)?;
+    for key in ["include", "numbers", "points", "name"] {
+        root[key].dedup_array();
+    }
+    let expected = from_str(
+        r#"
+include = ["/usr/lib", "/lib", "/opt/lib"]
+numbers = [1, 1.0, 2]
+points = [{ x = 1, y = 2 }, { x = 2, y = 1 }]
+name = "loader"
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn leaves() -> Result<()> {
+    let text = r#"
+title = "example"
+empty = {}
+point = { x = 1, y = 2 }
+
+[database]
+ports = [8000, 8001]
+connection.timeout = 30
+
+[[servers]]
+name = "alpha"
+
+[owner.contact]
+email = "tom@example.com"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(root.leaves().collect::<Vec<_>>(), root.flatten());
+    assert_eq!(root.leaves().nth(1).unwrap().0, "point.x");
+    assert_eq!(
+        Value::Integer(1).leaves().collect::<Vec<_>>(),
+        Value::Integer(1).flatten()
+    );
+    Ok(())
+}
+
+#[test]
+fn as_map() -> Result<()> {
+    let root = from_str("name = \"loader\"\n[paths]\nlib = \"/usr/lib\"")?;
+    let paths = root["paths"].as_map().unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths["lib"].as_str(), "/usr/lib");
+    assert_eq!(root["name"].as_map(), None);
+    Ok(())
+}
+
+#[test]
+fn map_array() -> Result<()> {
+    let mut root = from_str("hosts = [\"Alpha\", \"BETA\", 3]\nname = \"loader\"")?;
+    root["hosts"].map_array(|value| {
+        if let Value::String(string) = value {
+            *string = string.to_lowercase();
+        }
+    })?;
+    assert_eq!(
+        root["hosts"],
+        from_str("a = [\"alpha\", \"beta\", 3]")?["a"]
+    );
+
+    let err = root["name"].map_array(|_| {}).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnexpectedType {
+            expected: "array",
+            found: "string",
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn top_level_keys() -> Result<()> {
+    let text = r#"
+title = "example"
+
+[server]
+host = "localhost"
+
+[database.replica]
+port = 5432
+
+[[plugins]]
+name = "auth"
+"#;
+    let root = from_str(text)?;
+    assert_eq!(
+        root.top_level_keys(),
+        ["title", "server", "database", "plugins"]
+    );
+    assert!(root["title"].top_level_keys().is_empty());
+    Ok(())
+}
+
+#[test]
+fn retain_paths() -> Result<()> {
+    let text = r#"
+title = "example"
+
+[server]
+host = "localhost"
+port = 8080
+tls = { cert = "cert.pem", key = "key.pem" }
+
+[database]
+url = "postgres://localhost"
+pool = { min = 1, max = 10 }
+
+[logging]
+level = "info"
+"#;
+    let mut root = from_str(text)?;
+    root.retain_paths(&["server.tls.cert", "database.pool", "server.missing.key"]);
+    let expected = from_str(
+        r#"
+[server]
+tls = { cert = "cert.pem" }
+
+[database]
+pool = { min = 1, max = 10 }
+"#,
+    )?;
+    assert_eq!(root, expected);
+    Ok(())
+}
+
+#[test]
+fn type_histogram() -> Result<()> {
+    let text = r#"
+title = "example"
+ratio = 0.5
+
+[server]
+host = "localhost"
+ports = [8000, 8001]
+enabled = true
+
+[[plugins]]
+name = "auth"
+
+[[plugins]]
+name = "cache"
+"#;
+    let root = from_str(text)?;
+    let histogram = root.type_histogram();
+    assert_eq!(histogram.len(), 6);
+    assert_eq!(histogram["string"], 4);
+    assert_eq!(histogram["float"], 1);
+    assert_eq!(histogram["integer"], 2);
+    assert_eq!(histogram["boolean"], 1);
+    assert_eq!(histogram["array"], 2);
+    assert_eq!(histogram["table"], 4);
+    Ok(())
+}
+
+#[test]
+fn normalize_line_endings() -> Result<()> {
+    let text = "
+unix = \"\"\"\nfirst\nsecond\"\"\"
+windows = '''\r\nfirst\r\nsecond'''
+mixed = [\"a\\r\\nb\\nc\", \"lone\\rcr\"]
+";
+    let mut root = from_str(text)?;
+    root.normalize_line_endings(LineEnding::Lf);
+    assert_eq!(root["unix"].as_str(), "first\nsecond");
+    assert_eq!(root["windows"].as_str(), "first\nsecond");
+    assert_eq!(root["mixed"][0].as_str(), "a\nb\nc");
+    assert_eq!(root["mixed"][1].as_str(), "lone\rcr");
+
+    root.normalize_line_endings(LineEnding::CrLf);
+    assert_eq!(root["unix"].as_str(), "first\r\nsecond");
+    assert_eq!(root["windows"].as_str(), "first\r\nsecond");
+    assert_eq!(root["mixed"][0].as_str(), "a\r\nb\r\nc");
+    assert_eq!(root["mixed"][1].as_str(), "lone\rcr");
+    Ok(())
+}
+
+#[test]
+fn date_time_components() -> Result<()> {
+    let text = "
+odt = 1979-05-27T00:32:01.999-07:00
+ldt = 1979-05-27 07:32:02
+ld = 1979-05-27
+lt = 07:32:03.5
+int = 1979
+";
+    let root = from_str(text)?;
+    let components = |value: &Value| {
+        [
+            value.year(),
+            value.month(),
+            value.day(),
+            value.hour(),
+            value.minute(),
+            value.second(),
+        ]
+    };
+    assert_eq!(
+        components(&root["odt"]),
+        [Some(1979), Some(5), Some(27), Some(0), Some(32), Some(1)]
+    );
+    assert_eq!(
+        components(&root["ldt"]),
+        [Some(1979), Some(5), Some(27), Some(7), Some(32), Some(2)]
+    );
+    assert_eq!(
+        components(&root["ld"]),
+        [Some(1979), Some(5), Some(27), None, None, None]
+    );
+    assert_eq!(
+        components(&root["lt"]),
+        [None, None, None, Some(7), Some(32), Some(3)]
+    );
+    assert_eq!(components(&root["int"]), [None; 6]);
+    Ok(())
+}